@@ -0,0 +1,423 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, RwLock};
+
+use raur::SearchBy;
+use serde::de::{self, Deserializer as _, SeqAccess, Visitor};
+
+use crate::utils::AurDBFile;
+
+//------------------------------------------------------------------------------
+// CONST variables
+//------------------------------------------------------------------------------
+const MAX_RESULTS: usize = 5000;
+
+//------------------------------------------------------------------------------
+// STRUCT: AurDbEntry
+//------------------------------------------------------------------------------
+#[derive(Debug)]
+struct AurDbEntry {
+    name: String,
+    description: String,
+    offset: u64
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: DumpVisitor
+//------------------------------------------------------------------------------
+struct DumpVisitor<F: FnMut(raur::Package) -> io::Result<()>> {
+    on_package: F
+}
+
+impl<'de, F: FnMut(raur::Package) -> io::Result<()>> Visitor<'de> for DumpVisitor<F> {
+    type Value = usize;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an array of AUR packages")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(mut self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut count = 0;
+
+        // Process packages one at a time to avoid loading the whole dump in memory
+        while let Some(pkg) = seq.next_element::<raur::Package>()? {
+            (self.on_package)(pkg).map_err(de::Error::custom)?;
+
+            count += 1;
+        }
+
+        Ok(count)
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: AurDb
+//------------------------------------------------------------------------------
+#[derive(Debug)]
+pub struct AurDb {
+    entries: Vec<AurDbEntry>,
+    name_map: HashMap<String, usize>,
+    data_path: PathBuf
+}
+
+impl AurDb {
+    pub const NAMES_FILE: &str = "aur_packages";
+    const DATA_FILE: &str = "aur_meta";
+    const INDEX_FILE: &str = "aur_meta.idx";
+
+    //---------------------------------------
+    // Shared database function
+    //---------------------------------------
+    fn shared_db() -> &'static RwLock<Option<Arc<Self>>> {
+        static AUR_DB: LazyLock<RwLock<Option<Arc<AurDb>>>> = LazyLock::new(|| RwLock::new(None));
+
+        &AUR_DB
+    }
+
+    pub fn shared() -> Option<Arc<Self>> {
+        if let Some(aur_db) = Self::shared_db().read().unwrap().as_ref() {
+            return Some(Arc::clone(aur_db))
+        }
+
+        let aur_db = Arc::new(Self::open(AurDBFile::cache_dir()?).ok()?);
+
+        Self::shared_db().write().unwrap().replace(Arc::clone(&aur_db));
+
+        Some(aur_db)
+    }
+
+    pub fn invalidate() {
+        Self::shared_db().write().unwrap().take();
+    }
+
+    //---------------------------------------
+    // Exists function
+    //---------------------------------------
+    pub fn exists(dir: &Path) -> bool {
+        dir.join(Self::INDEX_FILE).is_file() && dir.join(Self::DATA_FILE).is_file()
+    }
+
+    //---------------------------------------
+    // Build function
+    //---------------------------------------
+    pub fn build<R: Read>(dump: R, dir: &Path) -> io::Result<usize> {
+        let tmp_path = |file: &str| dir.join(format!("{file}.tmp"));
+
+        let files = [Self::DATA_FILE, Self::INDEX_FILE, Self::NAMES_FILE];
+
+        let count = match Self::write_files(dump, tmp_path) {
+            Ok(count) => count,
+            Err(error) => {
+                // Remove partial files and keep previous database
+                for file in files {
+                    let _ = fs::remove_file(tmp_path(file));
+                }
+
+                return Err(error)
+            }
+        };
+
+        // Replace previous database files
+        for file in files {
+            fs::rename(tmp_path(file), dir.join(file))?;
+        }
+
+        Ok(count)
+    }
+
+    fn write_files<R: Read>(dump: R, tmp_path: impl Fn(&str) -> PathBuf) -> io::Result<usize> {
+        let mut names_writer = BufWriter::new(File::create(tmp_path(Self::NAMES_FILE))?);
+        let mut data_writer = BufWriter::new(File::create(tmp_path(Self::DATA_FILE))?);
+        let mut index_writer = BufWriter::new(File::create(tmp_path(Self::INDEX_FILE))?);
+
+        let mut offset: u64 = 0;
+
+        // Write one package per line to data file and its offset to index file
+        let visitor = DumpVisitor {
+            on_package: |pkg: raur::Package| {
+                let line = serde_json::to_string(&pkg).map_err(io::Error::other)? + "\n";
+
+                let description = pkg.description.as_deref().unwrap_or_default()
+                    .replace(['\t', '\n', '\r'], " ");
+
+                writeln!(names_writer, "{}", pkg.name)?;
+                writeln!(index_writer, "{}\t{offset}\t{description}", pkg.name)?;
+
+                data_writer.write_all(line.as_bytes())?;
+
+                offset += line.len() as u64;
+
+                Ok(())
+            }
+        };
+
+        let mut deserializer = serde_json::Deserializer::from_reader(BufReader::new(dump));
+
+        let count = deserializer.deserialize_seq(visitor).map_err(io::Error::other)?;
+
+        deserializer.end().map_err(io::Error::other)?;
+
+        names_writer.flush()?;
+        data_writer.flush()?;
+        index_writer.flush()?;
+
+        Ok(count)
+    }
+
+    //---------------------------------------
+    // Open function
+    //---------------------------------------
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        let index = fs::read_to_string(dir.join(Self::INDEX_FILE))?;
+
+        let entries: Vec<AurDbEntry> = index.lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+
+                Some(AurDbEntry {
+                    name: fields.next()?.to_owned(),
+                    offset: fields.next()?.parse().ok()?,
+                    description: fields.next().unwrap_or_default().to_owned()
+                })
+            })
+            .collect();
+
+        let name_map = entries.iter().enumerate()
+            .map(|(i, entry)| (entry.name.clone(), i))
+            .collect();
+
+        Ok(Self {
+            entries,
+            name_map,
+            data_path: dir.join(Self::DATA_FILE)
+        })
+    }
+
+    //---------------------------------------
+    // Info function
+    //---------------------------------------
+    pub fn info<S: AsRef<str>>(&self, names: &[S]) -> io::Result<Vec<raur::Package>> {
+        let mut reader = BufReader::new(File::open(&self.data_path)?);
+        let mut line = String::new();
+
+        let mut pkgs = Vec::with_capacity(names.len());
+
+        for name in names {
+            let Some(&index) = self.name_map.get(name.as_ref()) else {
+                continue
+            };
+
+            reader.seek(SeekFrom::Start(self.entries[index].offset))?;
+
+            line.clear();
+            reader.read_line(&mut line)?;
+
+            pkgs.push(serde_json::from_str(&line).map_err(io::Error::other)?);
+        }
+
+        Ok(pkgs)
+    }
+
+    //---------------------------------------
+    // Search function
+    //---------------------------------------
+    pub fn search(&self, term: &str, search_by: SearchBy) -> io::Result<Vec<raur::Package>> {
+        // Helper function
+        fn dep_name(dep: &str) -> &str {
+            dep.split(['<', '>', '=', ':']).next().unwrap_or_default().trim()
+        }
+
+        let term = term.to_lowercase();
+
+        let pkgs = match search_by {
+            SearchBy::Name | SearchBy::NameDesc => {
+                // Search index for matching names/descriptions
+                let names: Vec<&str> = self.entries.iter()
+                    .filter(|entry| {
+                        entry.name.to_lowercase().contains(&term)
+                            || (search_by == SearchBy::NameDesc && entry.description.to_lowercase().contains(&term))
+                    })
+                    .map(|entry| entry.name.as_str())
+                    .collect();
+
+                if names.len() > MAX_RESULTS {
+                    return Err(io::Error::other("Too many package results."))
+                }
+
+                self.info(&names)?
+            },
+            _ => {
+                // Scan data file for packages with matching field values
+                let matches_any = |values: &[String], is_dep: bool| {
+                    values.iter().any(|value| {
+                        let value = if is_dep { dep_name(value) } else { value.as_str() };
+
+                        value.to_lowercase() == term
+                    })
+                };
+
+                let reader = BufReader::new(File::open(&self.data_path)?);
+
+                let mut pkgs = vec![];
+
+                for line in reader.lines() {
+                    let line = line?;
+
+                    if !line.to_lowercase().contains(&term) {
+                        continue
+                    }
+
+                    let pkg: raur::Package = serde_json::from_str(&line).map_err(io::Error::other)?;
+
+                    let is_match = match search_by {
+                        SearchBy::Maintainer => pkg.maintainer.as_deref().is_some_and(|m| m.to_lowercase() == term),
+                        SearchBy::Submitter => pkg.submitter.as_deref().is_some_and(|s| s.to_lowercase() == term),
+                        SearchBy::Depends => matches_any(&pkg.depends, true),
+                        SearchBy::MakeDepends => matches_any(&pkg.make_depends, true),
+                        SearchBy::OptDepends => matches_any(&pkg.opt_depends, true),
+                        SearchBy::CheckDepends => matches_any(&pkg.check_depends, true),
+                        SearchBy::Provides => pkg.name.to_lowercase() == term || matches_any(&pkg.provides, true),
+                        SearchBy::Conflicts => matches_any(&pkg.conflicts, true),
+                        SearchBy::Replaces => matches_any(&pkg.replaces, true),
+                        SearchBy::Groups => matches_any(&pkg.groups, false),
+                        SearchBy::Keywords => matches_any(&pkg.keywords, false),
+                        SearchBy::CoMaintainers => matches_any(&pkg.co_maintainers, false),
+                        SearchBy::Name | SearchBy::NameDesc => unreachable!()
+                    };
+
+                    if is_match {
+                        pkgs.push(pkg);
+
+                        if pkgs.len() > MAX_RESULTS {
+                            return Err(io::Error::other("Too many package results."))
+                        }
+                    }
+                }
+
+                pkgs
+            }
+        };
+
+        Ok(pkgs)
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::TestDir;
+
+    // Subset of packages-meta-ext-v1.json from the AUR
+    const DUMP: &str = include_str!("../tests/fixtures/packages-meta-ext-v1.json");
+
+    fn build_db(dir: &TestDir) -> AurDb {
+        assert_eq!(AurDb::build(DUMP.as_bytes(), dir.path()).unwrap(), 4);
+
+        AurDb::open(dir.path()).unwrap()
+    }
+
+    fn names(pkgs: &[raur::Package]) -> Vec<&str> {
+        pkgs.iter().map(|pkg| pkg.name.as_str()).collect()
+    }
+
+    #[test]
+    fn build_writes_database_files() {
+        let dir = TestDir::new("aur-db-build");
+
+        build_db(&dir);
+
+        assert!(AurDb::exists(dir.path()));
+
+        let names = fs::read_to_string(dir.path().join(AurDb::NAMES_FILE)).unwrap();
+
+        assert_eq!(names, "yay\nyay-bin\nparu\npython-tabulate-git\n");
+
+        // Tabs in descriptions must not break index lines
+        let index = fs::read_to_string(dir.path().join(AurDb::INDEX_FILE)).unwrap();
+
+        assert_eq!(index.lines().count(), 4);
+        assert!(index.contains("python-tabulate-git\t"));
+        assert!(index.ends_with("\tPretty-print tabular data with tabs\n"));
+    }
+
+    #[test]
+    fn search_by_name_and_description() {
+        let dir = TestDir::new("aur-db-name");
+        let aur_db = build_db(&dir);
+
+        assert_eq!(names(&aur_db.search("YAY", SearchBy::Name).unwrap()), ["yay", "yay-bin"]);
+        assert_eq!(names(&aur_db.search("helper", SearchBy::Name).unwrap()), Vec::<&str>::new());
+        assert_eq!(names(&aur_db.search("helper", SearchBy::NameDesc).unwrap()), ["yay", "yay-bin", "paru"]);
+        assert_eq!(names(&aur_db.search("tabular", SearchBy::NameDesc).unwrap()), ["python-tabulate-git"]);
+    }
+
+    #[test]
+    fn search_by_depends_and_provides() {
+        let dir = TestDir::new("aur-db-deps");
+        let aur_db = build_db(&dir);
+
+        // Version constraints are ignored, partial names do not match
+        assert_eq!(names(&aur_db.search("pacman", SearchBy::Depends).unwrap()), ["yay", "yay-bin", "paru"]);
+        assert_eq!(names(&aur_db.search("pac", SearchBy::Depends).unwrap()), Vec::<&str>::new());
+        assert_eq!(names(&aur_db.search("libalpm.so", SearchBy::Depends).unwrap()), ["paru"]);
+        assert_eq!(names(&aur_db.search("go", SearchBy::MakeDepends).unwrap()), ["yay"]);
+        assert_eq!(names(&aur_db.search("bat", SearchBy::OptDepends).unwrap()), ["paru"]);
+
+        // Packages provide their own name
+        assert_eq!(names(&aur_db.search("yay", SearchBy::Provides).unwrap()), ["yay", "yay-bin"]);
+        assert_eq!(names(&aur_db.search("python-tabulate", SearchBy::Provides).unwrap()), ["python-tabulate-git"]);
+
+        assert_eq!(names(&aur_db.search("jguer-bot", SearchBy::CoMaintainers).unwrap()), ["yay-bin"]);
+        assert_eq!(names(&aur_db.search("rust", SearchBy::Keywords).unwrap()), ["paru"]);
+    }
+
+    #[test]
+    fn info_seeks_to_package_offsets() {
+        let dir = TestDir::new("aur-db-info");
+        let aur_db = build_db(&dir);
+
+        // Requested order is kept and unknown names are skipped
+        let pkgs = aur_db.info(&["python-tabulate-git", "missing", "yay", "paru"]).unwrap();
+
+        assert_eq!(names(&pkgs), ["python-tabulate-git", "yay", "paru"]);
+
+        assert_eq!(pkgs[0].maintainer, None);
+        assert_eq!(pkgs[1].version, "12.4.2-1");
+        assert_eq!(pkgs[1].depends, ["pacman>6.1", "git"]);
+        assert_eq!(pkgs[2].out_of_date, Some(1_735_000_000));
+        assert_eq!(pkgs[2].opt_depends, ["bat: colored pkgbuild printing", "devtools: build in chroot"]);
+
+        assert!(aur_db.info::<&str>(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn truncated_dump_keeps_previous_database() {
+        let dir = TestDir::new("aur-db-truncated");
+
+        build_db(&dir);
+
+        let truncated = &DUMP[..DUMP.len() / 2];
+
+        assert!(AurDb::build(truncated.as_bytes(), dir.path()).is_err());
+        assert!(AurDb::build("{\"not\": \"an array\"}".as_bytes(), dir.path()).is_err());
+
+        // Previous database is still complete and temporary files are removed
+        let aur_db = AurDb::open(dir.path()).unwrap();
+
+        assert_eq!(names(&aur_db.info(&["yay", "paru", "python-tabulate-git"]).unwrap()), ["yay", "paru", "python-tabulate-git"]);
+
+        let files: Vec<String> = fs::read_dir(dir.path()).unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect();
+
+        assert!(files.is_empty());
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::LazyLock;
+
+use gtk::{gio, glib};
+use gio::prelude::SettingsExt;
+
+use strum::{AsRefStr, EnumString, FromRepr};
+use which::which_global;
+use regex::Regex;
+
+use crate::{
+    APP_ID,
+    utils::{Pacman, ParuConf}
+};
+
+//------------------------------------------------------------------------------
+// ENUM: AurHelper
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Eq, PartialEq, Clone, Copy, glib::Enum, FromRepr, AsRefStr, EnumString)]
+#[strum(serialize_all = "lowercase")]
+#[repr(u32)]
+#[enum_type(name = "AurHelper")]
+pub enum AurHelper {
+    #[default]
+    Paru,
+    Yay,
+    Pikaur,
+    #[enum_value(name = "aurutils")]
+    Aurutils,
+}
+
+impl AurHelper {
+    //---------------------------------------
+    // Active helper function
+    //---------------------------------------
+    pub fn active() -> Self {
+        gio::Settings::new(APP_ID).string("aur-helper")
+            .parse()
+            .unwrap_or_default()
+    }
+
+    //---------------------------------------
+    // Path function
+    //---------------------------------------
+    pub fn path(self) -> Option<&'static PathBuf> {
+        static PATHS: LazyLock<[Option<PathBuf>; 4]> = LazyLock::new(|| {
+            [AurHelper::Paru, AurHelper::Yay, AurHelper::Pikaur, AurHelper::Aurutils]
+                .map(|helper| which_global(helper.binary()).ok())
+        });
+
+        PATHS[self as usize].as_ref()
+    }
+
+    fn binary(self) -> &'static str {
+        match self {
+            Self::Paru => "paru",
+            Self::Yay => "yay",
+            Self::Pikaur => "pikaur",
+            Self::Aurutils => "aur"
+        }
+    }
+
+    //---------------------------------------
+    // Command argument functions
+    //---------------------------------------
+    pub fn update_args(self) -> &'static [&'static str] {
+        match self {
+            Self::Paru => &["-Qu", "--mode=ap"],
+            Self::Yay | Self::Pikaur => &["-Qua"],
+            Self::Aurutils => &["repo", "--upgrades"]
+        }
+    }
+
+    pub fn can_print_pkgbuild(self) -> bool {
+        matches!(self, Self::Paru | Self::Yay) && self.path().is_some()
+    }
+
+    pub fn pkgbuild_args(self, base: &str) -> Option<[&str; 2]> {
+        match self {
+            Self::Paru | Self::Yay => Some(["-Gp", base]),
+            Self::Pikaur | Self::Aurutils => None
+        }
+    }
+
+    //---------------------------------------
+    // Clone directory functions
+    //---------------------------------------
+    pub fn clone_dirs(self, repo: &str, base: &str) -> Vec<PathBuf> {
+        let cache_dir = glib::user_cache_dir();
+
+        match self {
+            Self::Paru => {
+                let paru_dir = cache_dir.join("paru/clone");
+
+                vec![paru_dir.join("repo").join(repo).join(base), paru_dir.join(base)]
+            },
+            Self::Yay => {
+                vec![Self::yay_build_dir().unwrap_or_else(|| cache_dir.join("yay")).join(base)]
+            },
+            Self::Pikaur => {
+                vec![
+                    glib::user_data_dir().join("pikaur/aur_repos").join(base),
+                    cache_dir.join("pikaur/build").join(base)
+                ]
+            },
+            Self::Aurutils => {
+                let aur_dest = env::var("AURDEST")
+                    .map_or_else(|_| cache_dir.join("aurutils/sync"), PathBuf::from);
+
+                vec![aur_dest.join(base)]
+            }
+        }
+    }
+
+    fn yay_build_dir() -> Option<PathBuf> {
+        static EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r#""buildDir"\s*:\s*"([^"]+)""#)
+                .expect("Failed to compile Regex")
+        });
+
+        let config = fs::read_to_string(glib::user_config_dir().join("yay/config.json")).ok()?;
+
+        EXPR.captures(&config)
+            .map(|caps| PathBuf::from(&caps[1]))
+    }
+
+    //---------------------------------------
+    // Local repository functions
+    //---------------------------------------
+    pub fn repo_names(self) -> Vec<String> {
+        match self {
+            Self::Paru => ParuConf::repo_names(),
+            Self::Yay | Self::Pikaur | Self::Aurutils => {
+                Self::file_repos()
+                    .map(|(repo, _)| repo)
+                    .collect()
+            }
+        }
+    }
+
+    pub fn repo_dir(self, repo: &str) -> Option<PathBuf> {
+        match self {
+            Self::Paru => Some(ParuConf::repo_dir(repo)),
+            Self::Yay | Self::Pikaur | Self::Aurutils => {
+                Self::file_repos()
+                    .find(|(name, _)| name == repo)
+                    .map(|(_, dir)| dir)
+            }
+        }
+    }
+
+    pub fn local_pkg_map(self) -> HashMap<String, Rc<String>> {
+        match self {
+            Self::Paru => ParuConf::local_pkg_map(),
+            Self::Yay | Self::Pikaur | Self::Aurutils => {
+                Self::file_repos()
+                    .flat_map(|(repo, dir)| {
+                        let repo_rc = Rc::new(repo);
+
+                        fs::read_dir(dir)
+                            .into_iter()
+                            .flatten()
+                            .flatten()
+                            .filter_map(move |entry| {
+                                Self::package_file_name(&entry.file_name().to_string_lossy())
+                                    .map(|name| (name.to_owned(), Rc::clone(&repo_rc)))
+                            })
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    //---------------------------------------
+    // Local repository helper functions
+    //---------------------------------------
+    fn file_repos() -> impl Iterator<Item = (String, PathBuf)> {
+        let config = Pacman::config();
+
+        let arch = config.architecture.first()
+            .map_or("x86_64", String::as_str);
+
+        // Local repositories are pacman repositories with a file:// server (e.g. aur repo-add)
+        config.repos.iter()
+            .filter_map(move |repo| {
+                let path = repo.servers.iter()
+                    .find_map(|server| server.strip_prefix("file://"))?
+                    .replace("$repo", &repo.name)
+                    .replace("$arch", arch);
+
+                Some((repo.name.clone(), PathBuf::from(path)))
+            })
+    }
+
+    fn package_file_name(file_name: &str) -> Option<&str> {
+        // Package files are named {name}-{pkgver}-{pkgrel}-{arch}.pkg.tar.*
+        let (stem, ext) = file_name.split_once(".pkg.tar")?;
+
+        if ext.ends_with(".sig") {
+            return None
+        }
+
+        stem.rsplitn(4, '-').nth(3)
+            .filter(|name| !name.is_empty())
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn package_file_name_strips_version_and_arch() {
+        assert_eq!(AurHelper::package_file_name("paru-bin-2.0.4-1-x86_64.pkg.tar.zst"), Some("paru-bin"));
+        assert_eq!(AurHelper::package_file_name("ttf-font-1:1.0.r5.g1234-2-any.pkg.tar.xz"), Some("ttf-font"));
+        assert_eq!(AurHelper::package_file_name("foo-1.0-1-x86_64.pkg.tar"), Some("foo"));
+
+        assert_eq!(AurHelper::package_file_name("foo-1.0-1-x86_64.pkg.tar.zst.sig"), None);
+        assert_eq!(AurHelper::package_file_name("custom.db.tar.gz"), None);
+        assert_eq!(AurHelper::package_file_name("1.0-1-x86_64.pkg.tar.zst"), None);
+    }
+}
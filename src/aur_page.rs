@@ -0,0 +1,391 @@
+use std::io;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+use raur::Raur;
+use regex::Regex;
+use tokio_util::sync::CancellationToken;
+
+use crate::utils::{PkgbuildSource, TokioUtils};
+
+//------------------------------------------------------------------------------
+// STRUCT: AurComment
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct AurComment {
+    author: String,
+    date: String,
+    content: String,
+    pinned: bool
+}
+
+impl AurComment {
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn date(&self) -> &str {
+        &self.date
+    }
+
+    pub fn content(&self) -> &str {
+        &self.content
+    }
+
+    pub fn pinned(&self) -> bool {
+        self.pinned
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: AurPage
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct AurPage {
+    maintainer: Option<String>,
+    co_maintainers: Vec<String>,
+    submitter: Option<String>,
+    first_submitted: i64,
+    last_modified: i64,
+    keywords: Vec<String>,
+    out_of_date: Option<i64>,
+    flag_reason: Option<String>,
+    comments: Vec<AurComment>
+}
+
+impl AurPage {
+    const AUR_URL: &str = "https://aur.archlinux.org";
+
+    //---------------------------------------
+    // Base URL function
+    //---------------------------------------
+    pub fn url() -> String {
+        PkgbuildSource::setting("aur-url", Self::AUR_URL)
+    }
+
+    pub fn rpc_url() -> String {
+        Self::url() + "/rpc/"
+    }
+
+    //---------------------------------------
+    // Public getters
+    //---------------------------------------
+    pub fn maintainer(&self) -> Option<&str> {
+        self.maintainer.as_deref()
+    }
+
+    pub fn co_maintainers(&self) -> &[String] {
+        &self.co_maintainers
+    }
+
+    pub fn submitter(&self) -> Option<&str> {
+        self.submitter.as_deref()
+    }
+
+    pub fn first_submitted(&self) -> i64 {
+        self.first_submitted
+    }
+
+    pub fn last_modified(&self) -> i64 {
+        self.last_modified
+    }
+
+    pub fn keywords(&self) -> &[String] {
+        &self.keywords
+    }
+
+    pub fn out_of_date(&self) -> Option<i64> {
+        self.out_of_date
+    }
+
+    pub fn flag_reason(&self) -> Option<&str> {
+        self.flag_reason.as_deref()
+    }
+
+    pub fn comments(&self) -> &[AurComment] {
+        &self.comments
+    }
+
+    //---------------------------------------
+    // Fetch async function
+    //---------------------------------------
+    pub async fn fetch(aur_url: &str, name: &str, base: &str, token: CancellationToken) -> io::Result<Self> {
+        let aur_url = aur_url.trim_end_matches('/').to_owned();
+        let rpc_url = format!("{aur_url}/rpc/");
+
+        let name = name.to_owned();
+        let base = base.to_owned();
+
+        TokioUtils::runtime().spawn(
+            async move {
+                let request = async {
+                    let client = reqwest::Client::builder()
+                        .timeout(Duration::from_secs(10))
+                        .build()
+                        .map_err(io::Error::other)?;
+
+                    // Get package metadata from AUR RPC interface
+                    let pkg = raur::Handle::new_with_settings(client.clone(), rpc_url)
+                        .info(&[&name])
+                        .await
+                        .map_err(io::Error::other)?
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Package not found in AUR"))?;
+
+                    // Get package comments from AUR web page
+                    let html = client.get(format!("{aur_url}/packages/{name}"))
+                        .send()
+                        .await
+                        .and_then(reqwest::Response::error_for_status)
+                        .map_err(io::Error::other)?
+                        .text()
+                        .await
+                        .map_err(io::Error::other)?;
+
+                    // Get out of date flag reason if package is flagged
+                    let flag_reason = if pkg.out_of_date.is_some() {
+                        let response = client.get(format!("{aur_url}/pkgbase/{base}/flag-comment"))
+                            .send()
+                            .await
+                            .and_then(reqwest::Response::error_for_status);
+
+                        match response {
+                            Ok(response) => response.text().await.ok()
+                                .and_then(|html| Self::parse_flag_reason(&html)),
+                            Err(_) => None
+                        }
+                    } else {
+                        None
+                    };
+
+                    Ok(Self {
+                        maintainer: pkg.maintainer,
+                        co_maintainers: pkg.co_maintainers,
+                        submitter: pkg.submitter,
+                        first_submitted: pkg.first_submitted,
+                        last_modified: pkg.last_modified,
+                        keywords: pkg.keywords,
+                        out_of_date: pkg.out_of_date,
+                        flag_reason,
+                        comments: Self::parse_comments(&html)
+                    })
+                };
+
+                tokio::select! {
+                    result = request => result,
+                    () = token.cancelled() => {
+                        Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled by user"))
+                    }
+                }
+            }
+        )
+        .await
+        .expect("Failed to complete tokio task")
+    }
+
+    //---------------------------------------
+    // Parse comments function
+    //---------------------------------------
+    fn parse_comments(html: &str) -> Vec<AurComment> {
+        static COMMENT_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r#"(?s)<h4 id="comment-\d+"[^>]*>(.*?)</h4>\s*<div id="comment-\d+-content" class="article-content">\s*<div>(.*?)</div>\s*</div>"#)
+                .expect("Failed to compile Regex")
+        });
+
+        static DATE_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r#"class="date">([^<]+)<"#).expect("Failed to compile Regex")
+        });
+
+        // Pinned comments are listed before latest comments
+        let latest_start = html.find("Latest Comments").unwrap_or(0);
+
+        COMMENT_EXPR.captures_iter(html)
+            .map(|caps| {
+                let header = Self::html_to_text(&caps[1]);
+
+                let author = header.split_once(" commented on ")
+                    .map_or("Anonymous", |(author, _)| author)
+                    .to_owned();
+
+                let date = DATE_EXPR.captures(&caps[1])
+                    .map(|date_caps| Self::html_to_text(&date_caps[1]))
+                    .unwrap_or_default();
+
+                AurComment {
+                    author,
+                    date,
+                    content: Self::html_to_text(&caps[2]),
+                    pinned: caps.get(0).is_some_and(|m| m.start() < latest_start)
+                }
+            })
+            .collect()
+    }
+
+    //---------------------------------------
+    // Parse flag reason function
+    //---------------------------------------
+    fn parse_flag_reason(html: &str) -> Option<String> {
+        static EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"(?s)<blockquote[^>]*>(.*?)</blockquote>").expect("Failed to compile Regex")
+        });
+
+        EXPR.captures(html)
+            .map(|caps| Self::html_to_text(&caps[1]))
+            .filter(|reason| !reason.is_empty())
+    }
+
+    //---------------------------------------
+    // HTML to text helper function
+    //---------------------------------------
+    fn html_to_text(html: &str) -> String {
+        // Consume line break following tag in HTML source to avoid doubled lines
+        static BREAK_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"(?i)(<br\s*/?>|</li>)\n?").expect("Failed to compile Regex")
+        });
+
+        static PARAGRAPH_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"(?i)(</p>|</pre>)\n?").expect("Failed to compile Regex")
+        });
+
+        static TAG_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"<[^>]*>").expect("Failed to compile Regex")
+        });
+
+        static BLANK_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"\n\s*\n\s*\n+").expect("Failed to compile Regex")
+        });
+
+        let text = BREAK_EXPR.replace_all(html, "$1\n");
+        let text = PARAGRAPH_EXPR.replace_all(&text, "$1\n\n");
+        let text = TAG_EXPR.replace_all(&text, "");
+
+        let text = text
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#34;", "\"")
+            .replace("&#39;", "'")
+            .replace("&#x27;", "'")
+            .replace("&nbsp;", " ")
+            .replace("&amp;", "&");
+
+        let text = text.lines()
+            .map(str::trim_end)
+            .collect::<Vec<&str>>()
+            .join("\n");
+
+        BLANK_EXPR.replace_all(text.trim(), "\n\n").into_owned()
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::{TestServer, TestResponse};
+
+    // Package and flag comment pages saved from the AUR (trimmed)
+    const PACKAGE_HTML: &str = include_str!("../tests/fixtures/aur_package.html");
+    const FLAG_HTML: &str = include_str!("../tests/fixtures/aur_flag_comment.html");
+
+    const RPC_JSON: &str = r#"{"version":5,"type":"multiinfo","resultcount":1,"results":[{
+        "ID":1,"Name":"yay","PackageBaseID":2,"PackageBase":"yay","Version":"12.4.2-1",
+        "Description":"Yet another yogurt","URL":"https://github.com/Jguer/yay","NumVotes":2000,
+        "Popularity":20.5,"OutOfDate":1733011200,"Maintainer":"Jguer","Submitter":"Jguer",
+        "FirstSubmitted":1475688004,"LastModified":1733000000,"URLPath":"/cgit/aur.git/snapshot/yay.tar.gz",
+        "Keywords":["aur","helper"],"CoMaintainers":["jguer-bot"]
+    }]}"#;
+
+    #[test]
+    fn parse_comments_from_package_page() {
+        let comments = AurPage::parse_comments(PACKAGE_HTML);
+
+        assert_eq!(comments.len(), 3);
+
+        let pinned = &comments[0];
+
+        assert_eq!(pinned.author(), "Jguer");
+        assert_eq!(pinned.date(), "2023-03-14 09:26 (UTC)");
+        assert!(pinned.pinned());
+        assert_eq!(pinned.content(), "Before reporting a bug, please make sure you are on the latest version & read the FAQ.\n\nTo rebuild against a new <pacman> release:\n\nyay -S yay --rebuild");
+
+        let latest = &comments[1];
+
+        assert_eq!(latest.author(), "someuser");
+        assert_eq!(latest.date(), "2024-11-02 18:45 (UTC)");
+        assert!(!latest.pinned());
+        assert_eq!(latest.content(), "Fails to build with go 1.23:\nerror: can't find \"go.mod\"\n\nFixed by cleaning the build dir.");
+
+        assert_eq!(comments[2].author(), "(deleted)");
+        assert_eq!(comments[2].content(), "Thanks!");
+    }
+
+    #[test]
+    fn parse_comments_without_comments() {
+        assert!(AurPage::parse_comments("<html><body><h2>Package Details</h2></body></html>").is_empty());
+    }
+
+    #[test]
+    fn parse_flag_reason_from_flag_page() {
+        assert_eq!(
+            AurPage::parse_flag_reason(FLAG_HTML).as_deref(),
+            Some("Version 12.4.3 released & fixes #2500")
+        );
+
+        assert!(AurPage::parse_flag_reason("<blockquote class=\"flag-comment\">  </blockquote>").is_none());
+        assert!(AurPage::parse_flag_reason(PACKAGE_HTML).is_none());
+    }
+
+    #[test]
+    fn html_to_text_converts_markup() {
+        assert_eq!(AurPage::html_to_text("a<br>b<br/>c<BR />d"), "a\nb\nc\nd");
+        assert_eq!(AurPage::html_to_text("<p>one</p>\n<p>two</p>"), "one\n\ntwo");
+        assert_eq!(AurPage::html_to_text("line<br>\nnext"), "line\nnext");
+        assert_eq!(AurPage::html_to_text("<ul><li>x</li><li>y</li></ul>"), "x\ny");
+        assert_eq!(AurPage::html_to_text("&lt;tag&gt; &amp;lt; &quot;q&quot; &#39;s&#x27; a&nbsp;b"), "<tag> &lt; \"q\" 's' a b");
+        assert_eq!(AurPage::html_to_text("<p>a</p>\n\n\n\n<p>b</p>"), "a\n\nb");
+    }
+
+    fn rpc_server(rpc_json: &'static str) -> TestServer {
+        // RPC arguments are sent as POST form data
+        TestServer::start(move |request| {
+            match request.path.as_str() {
+                "/rpc/" => TestResponse::ok(rpc_json),
+                "/packages/yay" => TestResponse::ok(PACKAGE_HTML),
+                "/pkgbase/yay/flag-comment" => TestResponse::ok(FLAG_HTML),
+                _ => TestResponse::not_found()
+            }
+        })
+    }
+
+    #[test]
+    fn fetch_from_local_server() {
+        let server = rpc_server(RPC_JSON);
+
+        let page = TokioUtils::runtime()
+            .block_on(AurPage::fetch(&format!("{}/", server.url()), "yay", "yay", CancellationToken::new()))
+            .unwrap();
+
+        assert_eq!(page.maintainer(), Some("Jguer"));
+        assert_eq!(page.co_maintainers(), ["jguer-bot"]);
+        assert_eq!(page.keywords(), ["aur", "helper"]);
+        assert_eq!(page.out_of_date(), Some(1_733_011_200));
+        assert_eq!(page.flag_reason(), Some("Version 12.4.3 released & fixes #2500"));
+        assert_eq!(page.comments().len(), 3);
+    }
+
+    #[test]
+    fn fetch_missing_package() {
+        let server = rpc_server(r#"{"version":5,"type":"multiinfo","resultcount":0,"results":[]}"#);
+
+        let error = TokioUtils::runtime()
+            .block_on(AurPage::fetch(&server.url(), "missing", "missing", CancellationToken::new()))
+            .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+}
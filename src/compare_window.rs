@@ -0,0 +1,759 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+use gtk::{glib, gdk, pango};
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use glib::{clone, VariantTy};
+use gdk::{Key, ModifierType};
+
+use size::Size;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    pkg_object::PkgObject,
+    info_row::PropID,
+    line_diff::DiffKind
+};
+
+//------------------------------------------------------------------------------
+// CONST variables
+//------------------------------------------------------------------------------
+const MAX_LIST_ITEMS: usize = 50;
+
+const LOADING: &str = "Loading\u{2026}";
+const UNAVAILABLE: &str = "Unavailable";
+
+const COMPARE_ROWS: [CompareRow; 33] = [
+    CompareRow::Version,
+    CompareRow::Repository,
+    CompareRow::Prop(PropID::Popularity),
+    CompareRow::Prop(PropID::OutOfDate),
+    CompareRow::Prop(PropID::PackageUrl),
+    CompareRow::Prop(PropID::Url),
+    CompareRow::Prop(PropID::Groups),
+    CompareRow::Prop(PropID::PackageBase),
+    CompareRow::Prop(PropID::Provides),
+    CompareRow::Prop(PropID::Dependencies),
+    CompareRow::Prop(PropID::ArchDependencies),
+    CompareRow::Prop(PropID::Optional),
+    CompareRow::Prop(PropID::Make),
+    CompareRow::Prop(PropID::Check),
+    CompareRow::Prop(PropID::RequiredBy),
+    CompareRow::Prop(PropID::OptionalFor),
+    CompareRow::Prop(PropID::ConflictsWith),
+    CompareRow::Prop(PropID::Replaces),
+    CompareRow::Prop(PropID::Architecture),
+    CompareRow::Prop(PropID::Epoch),
+    CompareRow::Prop(PropID::Licenses),
+    CompareRow::Prop(PropID::Packager),
+    CompareRow::Prop(PropID::AurMaintainer),
+    CompareRow::Prop(PropID::Origin),
+    CompareRow::Prop(PropID::BuildDate),
+    CompareRow::Prop(PropID::InstallDate),
+    CompareRow::Prop(PropID::DownloadSize),
+    CompareRow::InstallSize,
+    CompareRow::Prop(PropID::DiskUsage),
+    CompareRow::Prop(PropID::InstallScript),
+    CompareRow::Prop(PropID::Validation),
+    CompareRow::Prop(PropID::Sources),
+    CompareRow::Prop(PropID::PgpKeys),
+];
+
+//------------------------------------------------------------------------------
+// ENUM: CompareRow
+//------------------------------------------------------------------------------
+#[derive(Debug, Eq, PartialEq, Clone, Copy, Hash)]
+enum CompareRow {
+    Version,
+    Repository,
+    InstallSize,
+    Prop(PropID),
+    Files,
+}
+
+impl CompareRow {
+    fn title(&self) -> &str {
+        match self {
+            Self::Version => "Version",
+            Self::Repository => "Repository",
+            Self::InstallSize => "Installed Size",
+            Self::Prop(id) => id.as_ref(),
+            Self::Files => "Files"
+        }
+    }
+
+    fn iter() -> impl Iterator<Item = Self> {
+        COMPARE_ROWS.into_iter().chain([Self::Files])
+    }
+}
+
+//------------------------------------------------------------------------------
+// ENUM: CompareValue
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+enum CompareValue {
+    Text(Option<String>),
+    List(Vec<String>),
+    Size(i64),
+    Unknown(&'static str),
+}
+
+impl PartialEq for CompareValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Text(a), Self::Text(b)) => a == b,
+            (Self::List(a), Self::List(b)) => a == b,
+            (Self::Size(a), Self::Size(b)) => a == b,
+            // Values not loaded are never equal, so differences are not hidden
+            _ => false
+        }
+    }
+}
+
+impl CompareValue {
+    //---------------------------------------
+    // To string function
+    //---------------------------------------
+    fn to_text(&self) -> String {
+        match self {
+            Self::Text(text) => text.clone().unwrap_or_else(|| String::from("None")),
+            Self::List(items) if items.is_empty() => String::from("None"),
+            Self::List(items) if items.len() > MAX_LIST_ITEMS => format!("{} items", items.len()),
+            Self::List(items) => items.join(", "),
+            Self::Size(size) => Self::size_string(*size),
+            Self::Unknown(status) => (*status).to_owned()
+        }
+    }
+
+    fn size_string(size: i64) -> String {
+        if size > 0 { Size::from_bytes(size).to_string() } else { String::from("None") }
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: CompareColumn
+//------------------------------------------------------------------------------
+#[derive(Debug)]
+struct CompareColumn {
+    pkg: PkgObject,
+    values: HashMap<CompareRow, CompareValue>,
+    token: CancellationToken
+}
+
+impl CompareColumn {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    fn new(pkg: &PkgObject) -> Self {
+        Self {
+            pkg: pkg.clone(),
+            values: CompareRow::iter()
+                .map(|row| (row, Self::pkg_value(pkg, row)))
+                .collect(),
+            token: CancellationToken::new()
+        }
+    }
+
+    //---------------------------------------
+    // Package value function
+    //---------------------------------------
+    fn pkg_value(pkg: &PkgObject, row: CompareRow) -> CompareValue {
+        // Helper functions
+        fn text(value: Option<&str>) -> CompareValue {
+            CompareValue::Text(value.map(ToOwned::to_owned))
+        }
+
+        fn list(value: &[String]) -> CompareValue {
+            CompareValue::List(value.to_vec())
+        }
+
+        match row {
+            CompareRow::Version => CompareValue::Text(Some(pkg.version())),
+            CompareRow::Repository => CompareValue::Text(Some(pkg.repository())),
+            CompareRow::InstallSize => CompareValue::Size(pkg.install_size()),
+            CompareRow::Files => {
+                // Files are only available for installed packages
+                if pkg.is_installed() { list(pkg.files()) } else { CompareValue::Text(None) }
+            },
+            CompareRow::Prop(id) => match id {
+                PropID::Popularity => text(pkg.popularity()),
+                PropID::OutOfDate => text(pkg.out_of_date_string().as_deref()),
+                PropID::PackageUrl => text(pkg.package_url().as_deref()),
+                PropID::Url => text(pkg.url()),
+                PropID::Groups => list(pkg.groups()),
+                PropID::PackageBase => text(Some(pkg.base())),
+                PropID::Dependencies => list(pkg.depends()),
+                PropID::Optional => list(pkg.optdepends()),
+                PropID::Make => list(pkg.makedepends()),
+                PropID::Check => list(pkg.checkdepends()),
+                PropID::RequiredBy => list(pkg.required_by()),
+                PropID::OptionalFor => list(pkg.optional_for()),
+                PropID::Provides => list(pkg.provides()),
+                PropID::ConflictsWith => list(pkg.conflicts()),
+                PropID::Replaces => list(pkg.replaces()),
+                PropID::Licenses => list(pkg.licenses()),
+                PropID::Architecture => text(pkg.architecture()),
+                PropID::Packager => text(pkg.packager()),
+                PropID::AurMaintainer => text(pkg.aur_maintainer()),
+                PropID::Origin => text(pkg.origin().as_deref()),
+                PropID::BuildDate => text(pkg.build_date_string().as_deref()),
+                PropID::InstallDate => text(pkg.install_date_string().as_deref()),
+                PropID::DownloadSize => CompareValue::Size(pkg.download_size()),
+                PropID::InstallScript => text(pkg.has_script()),
+                PropID::Validation => CompareValue::Text(Some(pkg.validation().to_string())),
+                PropID::Epoch => CompareValue::Text(pkg.version().split_once(':').map(|(epoch, _)| epoch.to_owned())),
+                // Filled in asynchronously from disk usage and SRCINFO
+                PropID::DiskUsage => {
+                    if pkg.is_installed() { CompareValue::Unknown(LOADING) } else { CompareValue::Size(0) }
+                },
+                PropID::ArchDependencies | PropID::Sources | PropID::PgpKeys => CompareValue::Unknown(LOADING)
+            }
+        }
+    }
+
+    //---------------------------------------
+    // Value function
+    //---------------------------------------
+    fn value(&self, row: CompareRow) -> &CompareValue {
+        self.values.get(&row)
+            .expect("Failed to retrieve compare value")
+    }
+}
+
+//------------------------------------------------------------------------------
+// MODULE: CompareWindow
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate, glib::Properties)]
+    #[properties(wrapper_type = super::CompareWindow)]
+    #[template(resource = "/com/github/PacView/ui/compare_window.ui")]
+    pub struct CompareWindow {
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) grid: TemplateChild<gtk::Grid>,
+
+        #[template_child]
+        pub(super) footer_label: TemplateChild<gtk::Label>,
+
+        #[property(get, set)]
+        differences: Cell<bool>,
+
+        pub(super) columns: RefCell<Vec<CompareColumn>>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for CompareWindow {
+        const NAME: &'static str = "CompareWindow";
+        type Type = super::CompareWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+
+            // Add key bindings
+            Self::bind_shortcuts(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for CompareWindow {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+
+            obj.update_view();
+        }
+    }
+
+    impl WidgetImpl for CompareWindow {}
+    impl WindowImpl for CompareWindow {}
+    impl AdwWindowImpl for CompareWindow {}
+
+    impl CompareWindow {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Differences property action
+            klass.install_property_action("compare.differences", "differences");
+
+            // Clear action
+            klass.install_action("compare.clear", None, |window, _, _| {
+                window.clear();
+            });
+
+            // Remove package action
+            klass.install_action("compare.remove", Some(VariantTy::UINT32), |window, _, param| {
+                let index = param
+                    .and_then(|param| param.get::<u32>())
+                    .expect("Failed to get u32 from variant") as usize;
+
+                let mut columns = window.imp().columns.borrow_mut();
+
+                if index < columns.len() {
+                    columns.remove(index).token.cancel();
+                }
+
+                drop(columns);
+
+                window.update_view();
+            });
+
+            // Add sync package action
+            klass.install_action("compare.add-sync", Some(VariantTy::UINT32), |window, _, param| {
+                let index = param
+                    .and_then(|param| param.get::<u32>())
+                    .expect("Failed to get u32 from variant") as usize;
+
+                let sync_pkg = window.imp().columns.borrow().get(index)
+                    .and_then(|column| column.pkg.sync_pkg());
+
+                if let Some(pkg) = sync_pkg {
+                    window.add_pkg(&pkg);
+                }
+            });
+
+            // Copy action
+            klass.install_action("compare.copy", None, |window, _, _| {
+                let columns = window.imp().columns.borrow();
+
+                if columns.is_empty() {
+                    return
+                }
+
+                let mut output = String::from("## Package Comparison\n|Property|");
+
+                for column in columns.iter() {
+                    write!(output, "{}|", column.pkg.name()).unwrap();
+                }
+
+                writeln!(output, "\n|---|{}", "---|".repeat(columns.len())).unwrap();
+
+                for row in CompareRow::iter().filter(|&row| window.is_row_visible(&columns, row)) {
+                    write!(output, "|{}|", row.title()).unwrap();
+
+                    for column in columns.iter() {
+                        write!(output, "{}|", column.value(row).to_text().replace('|', "\\|")).unwrap();
+                    }
+
+                    output.push('\n');
+                }
+
+                window.clipboard().set_text(&output);
+            });
+        }
+
+        //---------------------------------------
+        // Bind shortcuts
+        //---------------------------------------
+        fn bind_shortcuts(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Close window binding
+            klass.add_binding_action(Key::Escape, ModifierType::NO_MODIFIER_MASK, "window.close");
+
+            // Differences key binding
+            klass.add_binding_action(Key::D, ModifierType::CONTROL_MASK, "compare.differences");
+
+            // Copy key binding
+            klass.add_binding_action(Key::C, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "compare.copy");
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: CompareWindow
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct CompareWindow(ObjectSubclass<imp::CompareWindow>)
+        @extends adw::Window, gtk::Window, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Native, gtk::Root, gtk::ShortcutManager;
+}
+
+impl CompareWindow {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        // Differences property notify signal
+        self.connect_differences_notify(|window| {
+            window.update_view();
+        });
+    }
+
+    //---------------------------------------
+    // Row visible helper function
+    //---------------------------------------
+    fn is_row_visible(&self, columns: &[CompareColumn], row: CompareRow) -> bool {
+        if !self.differences() || columns.len() < 2 {
+            return true
+        }
+
+        let base = columns[0].value(row);
+
+        columns[1..].iter().any(|column| column.value(row) != base)
+    }
+
+    //---------------------------------------
+    // Value label helper function
+    //---------------------------------------
+    fn value_label(text: &str, css_class: Option<&str>) -> gtk::Label {
+        let label = gtk::Label::builder()
+            .label(text)
+            .xalign(0.0)
+            .wrap(true)
+            .wrap_mode(pango::WrapMode::WordChar)
+            .max_width_chars(40)
+            .selectable(true)
+            .build();
+
+        if let Some(css_class) = css_class {
+            label.add_css_class(css_class);
+        }
+
+        label
+    }
+
+    //---------------------------------------
+    // Value widget helper function
+    //---------------------------------------
+    fn value_widget(value: &CompareValue, base: Option<&CompareValue>) -> gtk::Widget {
+        let is_changed = base.is_some_and(|base| base != value);
+
+        match value {
+            CompareValue::Text(text) => {
+                let css_class = if text.is_none() { Some("dimmed") } else if is_changed { Some("accent") } else { None };
+
+                Self::value_label(text.as_deref().unwrap_or("None"), css_class).upcast()
+            },
+            CompareValue::Unknown(status) => Self::value_label(status, Some("dimmed")).upcast(),
+            CompareValue::Size(size) => {
+                let mut text = CompareValue::size_string(*size);
+
+                // Show size delta compared to first package
+                if let Some(&CompareValue::Size(base_size)) = base && *size != base_size {
+                    let delta = size - base_size;
+
+                    write!(text, " ({}{})", if delta > 0 { "+" } else { "-" }, Size::from_bytes(delta.abs())).unwrap();
+                }
+
+                Self::value_label(&text, if is_changed { Some("accent") } else { None }).upcast()
+            },
+            CompareValue::List(items) => {
+                let list_box = gtk::Box::new(gtk::Orientation::Vertical, 2);
+
+                let item_set: HashSet<&str> = items.iter().map(String::as_str).collect();
+
+                // Only compare with first package if its list is loaded
+                let base_list: Option<&[String]> = match base {
+                    Some(CompareValue::List(base_items)) => Some(base_items),
+                    _ => None
+                };
+
+                let base_items = base_list.unwrap_or_default();
+
+                let base_set: HashSet<&str> = base_items.iter().map(String::as_str).collect();
+
+                let is_summary = items.len() > MAX_LIST_ITEMS;
+
+                if items.is_empty() && base_items.is_empty() {
+                    list_box.append(&Self::value_label("None", Some("dimmed")));
+                } else if is_summary {
+                    list_box.append(&Self::value_label(&format!("{} items", items.len()), None));
+                }
+
+                // Show items added/removed compared to first package
+                let unchanged = items.iter()
+                    .filter(|item| (base_list.is_none() || base_set.contains(item.as_str())) && !is_summary)
+                    .map(|item| (item, DiffKind::Same));
+
+                let added = items.iter()
+                    .filter(|item| base_list.is_some() && !base_set.contains(item.as_str()))
+                    .map(|item| (item, DiffKind::Added));
+
+                let removed = base_items.iter()
+                    .filter(|item| !item_set.contains(item.as_str()))
+                    .map(|item| (item, DiffKind::Removed));
+
+                let entries: Vec<(&String, DiffKind)> = unchanged.chain(added).chain(removed).collect();
+
+                for &(item, kind) in entries.iter().take(MAX_LIST_ITEMS) {
+                    let label = match kind {
+                        DiffKind::Added => Self::value_label(&format!("+ {item}"), Some("success")),
+                        DiffKind::Removed => {
+                            let label = Self::value_label("", Some("error"));
+
+                            label.set_markup(&format!("<s>\u{2212} {}</s>", glib::markup_escape_text(item)));
+
+                            label
+                        },
+                        _ => Self::value_label(item, None)
+                    };
+
+                    list_box.append(&label);
+                }
+
+                if entries.len() > MAX_LIST_ITEMS {
+                    list_box.append(&Self::value_label(
+                        &format!("\u{2026} and {} more", entries.len() - MAX_LIST_ITEMS),
+                        Some("dimmed")
+                    ));
+                }
+
+                list_box.upcast()
+            }
+        }
+    }
+
+    //---------------------------------------
+    // Header widget helper function
+    //---------------------------------------
+    fn header_widget(pkg: &PkgObject, index: usize) -> gtk::Widget {
+        let header_box = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+
+        let label_box = gtk::Box::new(gtk::Orientation::Vertical, 2);
+        label_box.set_hexpand(true);
+
+        let name_label = gtk::Label::builder()
+            .label(pkg.name())
+            .xalign(0.0)
+            .css_classes(["heading"])
+            .build();
+
+        let status_label = gtk::Label::builder()
+            .label(if pkg.is_installed() { "installed" } else { "not installed" })
+            .xalign(0.0)
+            .css_classes(["caption", "dimmed"])
+            .build();
+
+        label_box.append(&name_label);
+        label_box.append(&status_label);
+
+        header_box.append(&label_box);
+
+        // Add button to compare installed package with sync database version
+        if pkg.is_installed() && pkg.repository() != "aur" && pkg.repository() != "local" {
+            let sync_button = gtk::Button::builder()
+                .icon_name("backup-compare-symbolic")
+                .tooltip_text("Compare with Sync Database Version")
+                .valign(gtk::Align::Start)
+                .action_name("compare.add-sync")
+                .action_target(&(index as u32).to_variant())
+                .css_classes(["flat", "circular"])
+                .build();
+
+            header_box.append(&sync_button);
+        }
+
+        let remove_button = gtk::Button::builder()
+            .icon_name("window-close-symbolic")
+            .tooltip_text("Remove from Comparison")
+            .valign(gtk::Align::Start)
+            .action_name("compare.remove")
+            .action_target(&(index as u32).to_variant())
+            .css_classes(["flat", "circular"])
+            .build();
+
+        header_box.append(&remove_button);
+
+        header_box.upcast()
+    }
+
+    //---------------------------------------
+    // Update view
+    //---------------------------------------
+    fn update_view(&self) {
+        let imp = self.imp();
+
+        let columns = imp.columns.borrow();
+
+        // Clear grid
+        while let Some(child) = imp.grid.first_child() {
+            imp.grid.remove(&child);
+        }
+
+        imp.stack.set_visible_child_name(if columns.is_empty() { "empty" } else { "view" });
+
+        self.action_set_enabled("compare.clear", !columns.is_empty());
+        self.action_set_enabled("compare.copy", !columns.is_empty());
+
+        // Add header row
+        for (i, column) in columns.iter().enumerate() {
+            imp.grid.attach(&Self::header_widget(&column.pkg, i), i as i32 + 1, 0, 1, 1);
+        }
+
+        // Add property rows
+        let mut grid_row = 1;
+        let mut n_differences = 0;
+
+        for row in CompareRow::iter() {
+            if let Some((base, rest)) = columns.split_first()
+                && rest.iter().any(|column| column.value(row) != base.value(row)) {
+                n_differences += 1;
+            }
+
+            if !self.is_row_visible(&columns, row) {
+                continue
+            }
+
+            let title_label = gtk::Label::builder()
+                .label(row.title())
+                .xalign(0.0)
+                .valign(gtk::Align::Start)
+                .css_classes(["dimmed"])
+                .build();
+
+            imp.grid.attach(&title_label, 0, grid_row, 1, 1);
+
+            for (i, column) in columns.iter().enumerate() {
+                let base = (i > 0).then(|| columns[0].value(row));
+
+                imp.grid.attach(&Self::value_widget(column.value(row), base), i as i32 + 1, grid_row, 1, 1);
+            }
+
+            grid_row += 1;
+        }
+
+        imp.footer_label.set_label(&format!("{} package{}, {n_differences} propert{} differ{}",
+            columns.len(),
+            if columns.len() == 1 { "" } else { "s" },
+            if n_differences == 1 { "y" } else { "ies" },
+            if n_differences == 1 { "s" } else { "" }
+        ));
+    }
+
+    //---------------------------------------
+    // Update async values function
+    //---------------------------------------
+    fn update_async_values(&self, pkg: &PkgObject, token: CancellationToken) {
+        glib::spawn_future_local(clone!(
+            #[weak(rename_to = window)] self,
+            #[weak] pkg,
+            async move {
+                let mut values: Vec<(CompareRow, CompareValue)> = vec![];
+
+                match pkg.srcinfo_future(token.clone()).await {
+                    Ok(srcinfo) => {
+                        let name = pkg.name();
+
+                        values.push((CompareRow::Prop(PropID::ArchDependencies), CompareValue::List(srcinfo.arch_depends(&name))));
+                        values.push((CompareRow::Prop(PropID::Sources), CompareValue::List(srcinfo.sources())));
+                        values.push((CompareRow::Prop(PropID::PgpKeys), CompareValue::List(srcinfo.validpgpkeys.clone())));
+
+                        // Fill in checkdepends and architecture missing from databases
+                        if pkg.checkdepends().is_empty() {
+                            values.push((CompareRow::Prop(PropID::Check), CompareValue::List(srcinfo.checkdepends())));
+                        }
+
+                        if pkg.architecture().is_none() {
+                            let arch = srcinfo.package_arch(&name).join(" ");
+
+                            values.push((CompareRow::Prop(PropID::Architecture), CompareValue::Text((!arch.is_empty()).then_some(arch))));
+                        }
+                    },
+                    Err(_) => {
+                        for id in [PropID::ArchDependencies, PropID::Sources, PropID::PgpKeys] {
+                            values.push((CompareRow::Prop(id), CompareValue::Unknown(UNAVAILABLE)));
+                        }
+                    }
+                }
+
+                if pkg.is_installed() {
+                    let usage = pkg.disk_usage_future().await
+                        .map_or(CompareValue::Unknown(UNAVAILABLE), |usage| CompareValue::Size(usage.total_size()));
+
+                    values.push((CompareRow::Prop(PropID::DiskUsage), usage));
+                }
+
+                // Package removed from comparison
+                if token.is_cancelled() {
+                    return
+                }
+
+                for column in window.imp().columns.borrow_mut().iter_mut().filter(|column| column.pkg == pkg) {
+                    column.values.extend(values.iter().cloned());
+                }
+
+                window.update_view();
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Public add package function
+    //---------------------------------------
+    pub fn add_pkg(&self, pkg: &PkgObject) {
+        let imp = self.imp();
+
+        // Skip packages already in comparison
+        let is_duplicate = imp.columns.borrow().iter()
+            .any(|column| {
+                column.pkg == *pkg || (column.pkg.name() == pkg.name()
+                    && column.pkg.version() == pkg.version()
+                    && column.pkg.repository() == pkg.repository()
+                    && column.pkg.is_installed() == pkg.is_installed())
+            });
+
+        if is_duplicate {
+            return
+        }
+
+        let column = CompareColumn::new(pkg);
+        let token = column.token.clone();
+
+        imp.columns.borrow_mut().push(column);
+
+        self.update_view();
+
+        self.update_async_values(pkg, token);
+    }
+
+    //---------------------------------------
+    // Public clear function
+    //---------------------------------------
+    pub fn clear(&self) {
+        for column in self.imp().columns.borrow_mut().drain(..) {
+            column.token.cancel();
+        }
+
+        self.update_view();
+    }
+
+    //---------------------------------------
+    // Show window
+    //---------------------------------------
+    pub fn show(&self) {
+        self.present();
+    }
+}
+
+impl Default for CompareWindow {
+    //---------------------------------------
+    // Default constructor
+    //---------------------------------------
+    fn default() -> Self {
+        glib::Object::builder().build()
+    }
+}
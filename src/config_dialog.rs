@@ -1,10 +1,17 @@
+use std::fs;
+use std::path::Path;
+
 use gtk::glib;
 use adw::subclass::prelude::*;
 use adw::prelude::*;
 use glib::VariantTy;
 
+use size::Size;
+use heck::ToTitleCase;
+
 use crate::{
     config_row::ConfigRow,
+    aur_helper::AurHelper,
     utils::AppInfoExt
 };
 
@@ -23,6 +30,9 @@ mod imp {
         #[template_child]
         pub(super) config_button: TemplateChild<gtk::Button>,
 
+        #[template_child]
+        pub(super) page: TemplateChild<adw::PreferencesPage>,
+
         #[template_child]
         pub(super) paths_group: TemplateChild<adw::PreferencesGroup>,
         #[template_child]
@@ -154,9 +164,81 @@ impl ConfigDialog {
         Self::add_row(group, "LocalFileSigLevel", &config.local_file_sig_level.join(" | "), None);
         Self::add_row(group, "RemoteFileSigLevel", &config.remote_file_sig_level.join(" | "), None);
 
+        // Add repository groups
+        let helper = AurHelper::active();
+        let helper_name = helper.as_ref().to_title_case();
+        let helper_repos = helper.repo_names();
+
+        for repo in &config.repos {
+            let group = Self::add_group(&imp.page, &repo.name,
+                &if helper_repos.contains(&repo.name) {
+                    format!("Pacman and {helper_name} Repository")
+                } else {
+                    String::from("Pacman Repository")
+                }
+            );
+
+            Self::add_row(&group, "Server", &Self::join_or_none(&repo.servers, "\n"), None);
+            Self::add_row(&group, "SigLevel", &Self::join_or_none(&repo.sig_level, " | "), None);
+            Self::add_row(&group, "Usage", &Self::join_or_none(&repo.usage, " | "), None);
+
+            // Add sync database rows
+            let db_file = Path::new(&config.db_path).join(format!("sync/{}.db", repo.name));
+
+            Self::add_row(&group, "Database", &db_file.display().to_string(), None);
+
+            if let Ok(metadata) = fs::metadata(&db_file) {
+                let modified = metadata.modified().ok()
+                    .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+                    .and_then(|duration| glib::DateTime::from_unix_local(duration.as_secs() as i64).ok())
+                    .and_then(|datetime| datetime.format("%c").ok())
+                    .map_or_else(|| String::from("Unknown"), |date| date.to_string());
+
+                Self::add_row(&group, "Database Size", &Size::from_bytes(metadata.len()).to_string(), None);
+                Self::add_row(&group, "Database Modified", &modified, None);
+            } else {
+                Self::add_row(&group, "Database Size", "Not found", None);
+            }
+        }
+
+        for repo in helper_repos.iter().filter(|&repo| !config.repos.iter().any(|r| r.name == *repo)) {
+            let group = Self::add_group(&imp.page, repo, &format!("{helper_name} Repository"));
+
+            if let Some(clone_dir) = helper.repo_dir(repo) {
+                Self::add_row(&group, "Clone Directory", &clone_dir.display().to_string(),
+                    clone_dir.exists().then_some("conf.path")
+                );
+            }
+        }
+
         dialog
     }
 
+    //---------------------------------------
+    // Add group helper function
+    //---------------------------------------
+    fn add_group(page: &adw::PreferencesPage, title: &str, description: &str) -> adw::PreferencesGroup {
+        let group = adw::PreferencesGroup::builder()
+            .title(title)
+            .description(description)
+            .build();
+
+        page.add(&group);
+
+        group
+    }
+
+    //---------------------------------------
+    // Join or none helper function
+    //---------------------------------------
+    fn join_or_none(values: &[String], separator: &str) -> String {
+        if values.is_empty() {
+            String::from("None")
+        } else {
+            values.join(separator)
+        }
+    }
+
     //---------------------------------------
     // Add row helper function
     //---------------------------------------
@@ -0,0 +1,713 @@
+use std::cell::{Cell, RefCell};
+use std::fmt::Write as _;
+use std::fs;
+
+use gtk::{glib, gio, gdk, pango};
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use glib::{clone, Propagation};
+use gdk::{Key, ModifierType};
+
+use itertools::Itertools;
+use size::Size;
+use heck::ToTitleCase;
+use strum::FromRepr;
+
+use crate::{
+    pkg_object::PkgObject,
+    utils::Pacman
+};
+
+//------------------------------------------------------------------------------
+// CONST variables
+//------------------------------------------------------------------------------
+const MAX_NODES: usize = 150;
+
+const PALETTE: [(f64, f64, f64); 7] = [
+    (0.384, 0.627, 0.918),
+    (0.341, 0.890, 0.537),
+    (0.973, 0.894, 0.361),
+    (1.000, 0.639, 0.282),
+    (0.965, 0.380, 0.318),
+    (0.753, 0.380, 0.796),
+    (0.710, 0.514, 0.353)
+];
+
+//------------------------------------------------------------------------------
+// ENUM: DiskUsageMode
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Eq, PartialEq, Clone, Copy, glib::Enum, FromRepr)]
+#[repr(u32)]
+#[enum_type(name = "DiskUsageMode")]
+pub enum DiskUsageMode {
+    #[default]
+    #[enum_value(name = "By Repository")]
+    Repository,
+    #[enum_value(name = "By Group")]
+    Group,
+    #[enum_value(name = "By Package")]
+    Package,
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: DiskNode
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+struct DiskNode {
+    label: String,
+    size: u64,
+    pkg_name: Option<String>,
+    children: Vec<DiskNode>,
+}
+
+impl DiskNode {
+    fn new(label: &str, size: u64, pkg_name: Option<&str>, children: Vec<DiskNode>) -> Self {
+        Self {
+            label: label.to_owned(),
+            size,
+            pkg_name: pkg_name.map(ToOwned::to_owned),
+            children
+        }
+    }
+
+    fn group(label: &str, mut children: Vec<DiskNode>) -> Self {
+        children.sort_unstable_by(|a, b| b.size.cmp(&a.size));
+
+        let size = children.iter().map(|node| node.size).sum();
+
+        Self::new(label, size, None, Self::collapse(children))
+    }
+
+    // Lump the smallest nodes together so the treemap stays readable
+    fn collapse(mut nodes: Vec<DiskNode>) -> Vec<DiskNode> {
+        if nodes.len() > MAX_NODES {
+            let rest = nodes.split_off(MAX_NODES - 1);
+
+            nodes.push(Self::group(&format!("{} Others", rest.len()), rest));
+        }
+
+        nodes
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: DiskLevel
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+struct DiskLevel {
+    title: String,
+    nodes: Vec<DiskNode>,
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: TreemapRect
+//------------------------------------------------------------------------------
+#[derive(Debug, Default, Clone, Copy)]
+struct TreemapRect {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+impl TreemapRect {
+    fn contains(&self, x: f64, y: f64) -> bool {
+        x >= self.x && x < self.x + self.width && y >= self.y && y < self.y + self.height
+    }
+
+    //---------------------------------------
+    // Squarified treemap layout (values must be sorted in descending order)
+    //---------------------------------------
+    fn squarify(values: &[f64], bounds: Self) -> Vec<Self> {
+        let total: f64 = values.iter().sum();
+
+        if total <= 0.0 || bounds.width <= 0.0 || bounds.height <= 0.0 {
+            return vec![Self::default(); values.len()];
+        }
+
+        let scale = bounds.width * bounds.height / total;
+        let areas: Vec<f64> = values.iter().map(|value| value * scale).collect();
+
+        let mut rects: Vec<Self> = Vec::with_capacity(areas.len());
+        let mut free = bounds;
+        let mut start = 0;
+
+        while start < areas.len() {
+            let side = free.width.min(free.height);
+
+            let worst = |row: &[f64]| -> f64 {
+                let sum: f64 = row.iter().sum();
+                let max = row.iter().copied().fold(f64::MIN, f64::max);
+                let min = row.iter().copied().fold(f64::MAX, f64::min);
+
+                (side * side * max / (sum * sum)).max((sum * sum) / (side * side * min))
+            };
+
+            let mut end = start + 1;
+
+            while end < areas.len() && worst(&areas[start..=end]) <= worst(&areas[start..end]) {
+                end += 1;
+            }
+
+            let row = &areas[start..end];
+            let sum: f64 = row.iter().sum();
+
+            if free.width >= free.height {
+                let width = (sum / free.height).min(free.width);
+                let mut y = free.y;
+
+                for area in row {
+                    let height = area / width;
+
+                    rects.push(Self { x: free.x, y, width, height });
+
+                    y += height;
+                }
+
+                free.x += width;
+                free.width = (free.width - width).max(0.0);
+            } else {
+                let height = (sum / free.width).min(free.height);
+                let mut x = free.x;
+
+                for area in row {
+                    let width = area / height;
+
+                    rects.push(Self { x, y: free.y, width, height });
+
+                    x += width;
+                }
+
+                free.y += height;
+                free.height = (free.height - height).max(0.0);
+            }
+
+            start = end;
+        }
+
+        rects
+    }
+}
+
+//------------------------------------------------------------------------------
+// MODULE: DiskUsageWindow
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate, glib::Properties)]
+    #[properties(wrapper_type = super::DiskUsageWindow)]
+    #[template(resource = "/com/github/PacView/ui/disk_usage_window.ui")]
+    pub struct DiskUsageWindow {
+        #[template_child]
+        pub(super) window_title: TemplateChild<adw::WindowTitle>,
+        #[template_child]
+        pub(super) mode_dropdown: TemplateChild<gtk::DropDown>,
+
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) draw_area: TemplateChild<gtk::DrawingArea>,
+
+        #[template_child]
+        pub(super) footer_label: TemplateChild<gtk::Label>,
+
+        #[property(get, set)]
+        is_loaded: Cell<bool>,
+
+        pub(super) packages: RefCell<Vec<PkgObject>>,
+        pub(super) levels: RefCell<Vec<DiskLevel>>,
+        pub(super) levels_generation: Cell<u32>,
+        pub(super) rects: RefCell<Vec<TreemapRect>>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for DiskUsageWindow {
+        const NAME: &'static str = "DiskUsageWindow";
+        type Type = super::DiskUsageWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            DiskUsageMode::ensure_type();
+
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+
+            // Add key bindings
+            Self::bind_shortcuts(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for DiskUsageWindow {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+            obj.setup_widgets();
+            obj.setup_controllers();
+        }
+    }
+
+    impl WidgetImpl for DiskUsageWindow {}
+    impl WindowImpl for DiskUsageWindow {}
+    impl AdwWindowImpl for DiskUsageWindow {}
+
+    impl DiskUsageWindow {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Back action
+            klass.install_action("disk.back", None, |window, _, _| {
+                let imp = window.imp();
+
+                if imp.levels.borrow().len() > 1 {
+                    imp.levels.borrow_mut().pop();
+                    imp.levels_generation.set(imp.levels_generation.get().wrapping_add(1));
+
+                    window.update_view();
+                }
+            });
+
+            // Copy action
+            klass.install_action("disk.copy", None, |window, _, _| {
+                let levels = window.imp().levels.borrow();
+
+                if let Some(level) = levels.last() {
+                    let mut output = format!("## Disk Usage: {}\n|Name|Size|\n|---|---|\n",
+                        levels.iter().map(|level| level.title.as_str()).join(" / ")
+                    );
+
+                    for node in &level.nodes {
+                        writeln!(output, "|{label}|{size}|",
+                            label=node.label,
+                            size=Size::from_bytes(node.size)
+                        )
+                        .unwrap();
+                    }
+
+                    window.clipboard().set_text(&output);
+                }
+            });
+        }
+
+        //---------------------------------------
+        // Bind shortcuts
+        //---------------------------------------
+        fn bind_shortcuts(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Close window binding
+            klass.add_binding_action(Key::Escape, ModifierType::NO_MODIFIER_MASK, "window.close");
+
+            // Back key bindings
+            klass.add_binding_action(Key::BackSpace, ModifierType::NO_MODIFIER_MASK, "disk.back");
+            klass.add_binding_action(Key::Left, ModifierType::ALT_MASK, "disk.back");
+
+            // Copy key binding
+            klass.add_binding_action(Key::C, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "disk.copy");
+
+            // Mode key bindings
+            klass.add_binding(Key::R, ModifierType::ALT_MASK, |window| {
+                window.imp().mode_dropdown.set_selected(DiskUsageMode::Repository as u32);
+
+                Propagation::Stop
+            });
+
+            klass.add_binding(Key::G, ModifierType::ALT_MASK, |window| {
+                window.imp().mode_dropdown.set_selected(DiskUsageMode::Group as u32);
+
+                Propagation::Stop
+            });
+
+            klass.add_binding(Key::P, ModifierType::ALT_MASK, |window| {
+                window.imp().mode_dropdown.set_selected(DiskUsageMode::Package as u32);
+
+                Propagation::Stop
+            });
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: DiskUsageWindow
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct DiskUsageWindow(ObjectSubclass<imp::DiskUsageWindow>)
+        @extends adw::Window, gtk::Window, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Native, gtk::Root, gtk::ShortcutManager;
+}
+
+impl DiskUsageWindow {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        let imp = self.imp();
+
+        // Mode dropdown selected property notify signal
+        imp.mode_dropdown.connect_selected_notify(clone!(
+            #[weak(rename_to = window)] self,
+            move |_| {
+                if window.is_loaded() {
+                    window.build_root_level();
+                }
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Setup widgets
+    //---------------------------------------
+    fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        // Connect drawing area draw function
+        imp.draw_area.set_draw_func(clone!(
+            #[weak(rename_to = window)] self,
+            move |area, context, width, height| {
+                let imp = window.imp();
+
+                let levels = imp.levels.borrow();
+
+                let Some(level) = levels.last() else {
+                    return
+                };
+
+                let values: Vec<f64> = level.nodes.iter()
+                    .map(|node| node.size as f64)
+                    .collect();
+
+                let rects = TreemapRect::squarify(&values, TreemapRect {
+                    x: 0.0,
+                    y: 0.0,
+                    width: f64::from(width),
+                    height: f64::from(height)
+                });
+
+                for (i, (node, rect)) in level.nodes.iter().zip(&rects).enumerate() {
+                    if rect.width < 1.0 || rect.height < 1.0 {
+                        continue;
+                    }
+
+                    // Fill node rectangle
+                    let (red, green, blue) = PALETTE[i % PALETTE.len()];
+
+                    context.set_source_rgb(red, green, blue);
+                    context.rectangle(rect.x + 1.0, rect.y + 1.0, rect.width - 2.0, rect.height - 2.0);
+                    let _ = context.fill();
+
+                    // Show node label if it fits
+                    if rect.width > 48.0 && rect.height > 36.0 {
+                        let layout = area.create_pango_layout(Some(
+                            &format!("{}\n{}", node.label, Size::from_bytes(node.size))
+                        ));
+
+                        layout.set_width(((rect.width - 12.0) as i32) * pango::SCALE);
+                        layout.set_height(((rect.height - 12.0) as i32) * pango::SCALE);
+                        layout.set_ellipsize(pango::EllipsizeMode::End);
+
+                        context.save().unwrap();
+                        context.rectangle(rect.x, rect.y, rect.width, rect.height);
+                        context.clip();
+
+                        context.set_source_rgba(0.0, 0.0, 0.0, 0.8);
+                        context.move_to(rect.x + 6.0, rect.y + 6.0);
+
+                        pangocairo::functions::show_layout(context, &layout);
+
+                        context.restore().unwrap();
+                    }
+                }
+
+                imp.rects.replace(rects);
+            }
+        ));
+
+        // Set initial focus on drawing area
+        imp.draw_area.grab_focus();
+    }
+
+    //---------------------------------------
+    // Setup controllers
+    //---------------------------------------
+    fn setup_controllers(&self) {
+        let imp = self.imp();
+
+        // Drawing area click gesture
+        let click_gesture = gtk::GestureClick::builder()
+            .button(gdk::BUTTON_PRIMARY)
+            .build();
+
+        click_gesture.connect_released(clone!(
+            #[weak(rename_to = window)] self,
+            move |_, n, x, y| {
+                if n == 1 && let Some(index) = window.node_index_at_xy(x, y) {
+                    window.drill_down(index);
+                }
+            }
+        ));
+
+        imp.draw_area.add_controller(click_gesture);
+
+        // Drawing area secondary click gesture
+        let back_gesture = gtk::GestureClick::builder()
+            .button(gdk::BUTTON_SECONDARY)
+            .build();
+
+        back_gesture.connect_released(clone!(
+            #[weak(rename_to = window)] self,
+            move |_, _, _, _| {
+                window.activate_action("disk.back", None).unwrap();
+            }
+        ));
+
+        imp.draw_area.add_controller(back_gesture);
+
+        // Drawing area tooltip
+        imp.draw_area.connect_query_tooltip(clone!(
+            #[weak(rename_to = window)] self,
+            #[upgrade_or] false,
+            move |_, x, y, _, tooltip| {
+                let imp = window.imp();
+
+                window.node_index_at_xy(f64::from(x), f64::from(y))
+                    .and_then(|index| {
+                        imp.levels.borrow().last()
+                            .and_then(|level| level.nodes.get(index).cloned())
+                    })
+                    .is_some_and(|node| {
+                        tooltip.set_text(Some(&format!("{}\n{}", node.label, Size::from_bytes(node.size))));
+
+                        true
+                    })
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Node index helper function
+    //---------------------------------------
+    fn node_index_at_xy(&self, x: f64, y: f64) -> Option<usize> {
+        self.imp().rects.borrow().iter()
+            .position(|rect| rect.contains(x, y))
+    }
+
+    //---------------------------------------
+    // Update view
+    //---------------------------------------
+    fn update_view(&self) {
+        let imp = self.imp();
+
+        let levels = imp.levels.borrow();
+
+        if let Some(level) = levels.last() {
+            let total: u64 = level.nodes.iter().map(|node| node.size).sum();
+
+            imp.window_title.set_subtitle(&levels.iter().map(|level| level.title.as_str()).join(" › "));
+
+            imp.footer_label.set_label(&format!("{} item{} — {}",
+                level.nodes.len(),
+                if level.nodes.len() == 1 { "" } else { "s" },
+                Size::from_bytes(total)
+            ));
+
+            imp.stack.set_visible_child_name(if level.nodes.is_empty() { "empty" } else { "view" });
+        }
+
+        self.action_set_enabled("disk.back", levels.len() > 1);
+
+        imp.rects.borrow_mut().clear();
+        imp.draw_area.queue_draw();
+    }
+
+    //---------------------------------------
+    // Build root level
+    //---------------------------------------
+    fn build_root_level(&self) {
+        let imp = self.imp();
+
+        let mode = DiskUsageMode::from_repr(imp.mode_dropdown.selected())
+            .unwrap_or_default();
+
+        let pkg_node = |pkg: &PkgObject| {
+            DiskNode::new(&pkg.name(), pkg.install_size() as u64, Some(&pkg.name()), vec![])
+        };
+
+        let packages = imp.packages.borrow();
+
+        let root = match mode {
+            DiskUsageMode::Repository => {
+                let nodes: Vec<DiskNode> = packages.iter()
+                    .into_group_map_by(|pkg| pkg.repository())
+                    .into_iter()
+                    .map(|(repo, pkgs)| {
+                        let label = if repo == "aur" { repo.to_uppercase() } else { repo.to_title_case() };
+
+                        DiskNode::group(&label, pkgs.into_iter().map(pkg_node).collect())
+                    })
+                    .collect();
+
+                DiskNode::group("Installed Packages", nodes)
+            },
+            DiskUsageMode::Group => {
+                let nodes: Vec<DiskNode> = packages.iter()
+                    .flat_map(|pkg| {
+                        if pkg.groups().is_empty() {
+                            vec![(String::from("No Group"), pkg)]
+                        } else {
+                            pkg.groups().iter().map(|group| (group.clone(), pkg)).collect()
+                        }
+                    })
+                    .into_group_map()
+                    .into_iter()
+                    .map(|(group, pkgs)| {
+                        DiskNode::group(&group, pkgs.into_iter().map(pkg_node).collect())
+                    })
+                    .collect();
+
+                DiskNode::group("Installed Packages", nodes)
+            },
+            DiskUsageMode::Package => {
+                DiskNode::group("Installed Packages", packages.iter().map(pkg_node).collect())
+            }
+        };
+
+        imp.levels.replace(vec![DiskLevel { title: root.label, nodes: root.children }]);
+        imp.levels_generation.set(imp.levels_generation.get().wrapping_add(1));
+
+        self.update_view();
+    }
+
+    //---------------------------------------
+    // Drill down into node
+    //---------------------------------------
+    fn drill_down(&self, index: usize) {
+        let imp = self.imp();
+
+        let Some(node) = imp.levels.borrow().last()
+            .and_then(|level| level.nodes.get(index).cloned()) else {
+                return
+            };
+
+        if !node.children.is_empty() {
+            imp.levels.borrow_mut().push(DiskLevel { title: node.label, nodes: node.children });
+            imp.levels_generation.set(imp.levels_generation.get().wrapping_add(1));
+
+            self.update_view();
+        } else if let Some(pkg) = node.pkg_name.as_ref()
+            .and_then(|name| imp.packages.borrow().iter().find(|pkg| &pkg.name() == name).cloned()) {
+                self.load_package_files(&pkg);
+            }
+    }
+
+    //---------------------------------------
+    // Load package largest files
+    //---------------------------------------
+    fn load_package_files(&self, pkg: &PkgObject) {
+        let imp = self.imp();
+
+        imp.stack.set_visible_child_name("loading");
+
+        // Invalidate pending loads, result is discarded if levels change while loading
+        let generation = imp.levels_generation.get().wrapping_add(1);
+
+        imp.levels_generation.set(generation);
+
+        let root_dir = Pacman::config().root_dir.clone();
+        let files: Vec<String> = pkg.files().to_vec();
+        let pkg_name = pkg.name();
+
+        glib::spawn_future_local(clone!(
+            #[weak(rename_to = window)] self,
+            async move {
+                let nodes = gio::spawn_blocking(move || {
+                    let nodes: Vec<DiskNode> = files.iter()
+                        .filter(|file| !file.ends_with('/'))
+                        .filter_map(|file| {
+                            let path = root_dir.clone() + file;
+
+                            fs::symlink_metadata(&path).ok()
+                                .filter(fs::Metadata::is_file)
+                                .map(|metadata| DiskNode::new(&path, metadata.len(), None, vec![]))
+                        })
+                        .collect();
+
+                    DiskNode::group("", nodes).children
+                })
+                .await
+                .unwrap_or_default();
+
+                let imp = window.imp();
+
+                if imp.levels_generation.get() != generation {
+                    return
+                }
+
+                imp.levels.borrow_mut().push(DiskLevel { title: pkg_name, nodes });
+
+                window.update_view();
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Populate window
+    //---------------------------------------
+    fn populate(&self, pkg_model: &gio::ListStore) {
+        let imp = self.imp();
+
+        let packages: Vec<PkgObject> = pkg_model.iter::<PkgObject>()
+            .flatten()
+            .filter(PkgObject::is_installed)
+            .collect();
+
+        imp.packages.replace(packages);
+
+        self.build_root_level();
+    }
+
+    //---------------------------------------
+    // Show window
+    //---------------------------------------
+    pub fn show(&self, pkg_model: &gio::ListStore) {
+        self.present();
+
+        glib::idle_add_local_once(clone!(
+            #[weak(rename_to = window)] self,
+            #[weak] pkg_model,
+            move || {
+                if !window.is_loaded() {
+                    window.set_is_loaded(true);
+
+                    window.populate(&pkg_model);
+                }
+            }
+        ));
+    }
+}
+
+impl Default for DiskUsageWindow {
+    //---------------------------------------
+    // Default constructor
+    //---------------------------------------
+    fn default() -> Self {
+        glib::Object::builder().build()
+    }
+}
@@ -1,8 +1,12 @@
 use gtk::{glib, gdk};
 use adw::{prelude::*, subclass::prelude::*};
 use gdk::{Key, ModifierType};
+use glib::clone;
 
-use crate::pkg_object::PkgObject;
+use crate::{
+    pkg_object::PkgObject,
+    keyring::{PgpSignature, KeyringKey}
+};
 
 //------------------------------------------------------------------------------
 // MODULE: HashWindow
@@ -16,6 +20,20 @@ mod imp {
     #[derive(Default, gtk::CompositeTemplate)]
     #[template(resource = "/com/github/PacView/ui/hash_window.ui")]
     pub struct HashWindow {
+        #[template_child]
+        pub(super) signer_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub(super) trust_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub(super) expiry_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub(super) fingerprint_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub(super) key_id_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub(super) sig_date_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
+        pub(super) algorithm_row: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub(super) base64_row: TemplateChild<adw::ActionRow>,
         #[template_child]
@@ -82,22 +100,87 @@ impl HashWindow {
 
         let imp = obj.imp();
 
-        // Helper closure
-        let update_row = |row: &adw::ActionRow, hash: Option<&str> | {
-            if let Some(hash) = hash {
-                row.set_visible(true);
-                row.set_subtitle(hash);
-            } else {
-                row.set_visible(false);
-            }
-        };
-
         let hashes = pkg.hashes();
 
-        update_row(&imp.base64_row, hashes.base64_sig());
-        update_row(&imp.sha256_row, hashes.sha256sum());
-        update_row(&imp.md5_row, hashes.md5sum());
+        Self::update_row(&imp.base64_row, hashes.base64_sig());
+        Self::update_row(&imp.sha256_row, hashes.sha256sum());
+        Self::update_row(&imp.md5_row, hashes.md5sum());
+
+        // Decode signature
+        if let Some(signature) = hashes.base64_sig().and_then(PgpSignature::from_base64) {
+            Self::update_row(&imp.fingerprint_row, signature.fingerprint());
+            Self::update_row(&imp.key_id_row, signature.key_id());
+            Self::update_row(&imp.sig_date_row, Self::date_to_string(signature.created()).as_deref());
+            Self::update_row(&imp.algorithm_row, Some(&signature.algorithm()));
+
+            obj.update_keyring(&signature);
+        }
 
         obj
     }
+
+    //---------------------------------------
+    // Update row helper function
+    //---------------------------------------
+    fn update_row(row: &adw::ActionRow, value: Option<&str>) {
+        if let Some(value) = value {
+            row.set_visible(true);
+            row.set_subtitle(value);
+        } else {
+            row.set_visible(false);
+        }
+    }
+
+    //---------------------------------------
+    // Date to string helper function
+    //---------------------------------------
+    fn date_to_string(date: Option<i64>) -> Option<glib::GString> {
+        date.and_then(|date| {
+            glib::DateTime::from_unix_local(date).ok()
+                .and_then(|datetime| datetime.format("%c").ok())
+        })
+    }
+
+    //---------------------------------------
+    // Update keyring function
+    //---------------------------------------
+    fn update_keyring(&self, signature: &PgpSignature) {
+        let Some(issuer) = signature.issuer().map(ToOwned::to_owned) else {
+            return
+        };
+
+        let imp = self.imp();
+
+        Self::update_row(&imp.signer_row, Some("Searching pacman keyring\u{2026}"));
+
+        glib::spawn_future_local(clone!(
+            #[weak(rename_to = window)] self,
+            async move {
+                let imp = window.imp();
+
+                match KeyringKey::find(&issuer).await {
+                    Ok(Some(key)) => {
+                        Self::update_row(&imp.signer_row, Some(key.owner()));
+                        Self::update_row(&imp.trust_row, Some(key.trust()));
+                        Self::update_row(&imp.fingerprint_row, Some(key.fingerprint()));
+
+                        let expiry = Self::date_to_string(key.expires());
+
+                        Self::update_row(&imp.expiry_row, Some(expiry.as_deref().unwrap_or("Never")));
+
+                        imp.trust_row.add_css_class(if key.is_trusted() { "success" } else { "warning" });
+                    },
+                    Ok(None) => {
+                        Self::update_row(&imp.signer_row, Some("Key not found in pacman keyring"));
+                        Self::update_row(&imp.trust_row, Some("Unknown"));
+
+                        imp.trust_row.add_css_class("error");
+                    },
+                    Err(error) => {
+                        Self::update_row(&imp.signer_row, Some(&format!("Failed to read pacman keyring: {error}")));
+                    }
+                }
+            }
+        ));
+    }
 }
@@ -0,0 +1,75 @@
+use std::cell::{Cell, RefCell};
+
+use gtk::glib;
+use gtk::subclass::prelude::*;
+use gtk::prelude::ObjectExt;
+
+//------------------------------------------------------------------------------
+// ENUM: HealthKind
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Eq, PartialEq, Ord, PartialOrd, Clone, Copy, glib::Enum)]
+#[repr(u32)]
+#[enum_type(name = "HealthKind")]
+pub enum HealthKind {
+    #[default]
+    Broken,
+    Conflict,
+    Optional,
+}
+
+//------------------------------------------------------------------------------
+// MODULE: HealthObject
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::HealthObject)]
+    pub struct HealthObject {
+        #[property(get, set, construct_only, builder(HealthKind::default()))]
+        kind: Cell<HealthKind>,
+        #[property(get, set, construct_only)]
+        section: RefCell<String>,
+        #[property(get, set, construct_only)]
+        package: RefCell<String>,
+        #[property(get, set, construct_only)]
+        details: RefCell<String>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for HealthObject {
+        const NAME: &'static str = "HealthObject";
+        type Type = super::HealthObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for HealthObject {}
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: HealthObject
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct HealthObject(ObjectSubclass<imp::HealthObject>);
+}
+
+impl HealthObject {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    pub fn new(kind: HealthKind, section: &str, package: &str, details: &str) -> Self {
+        // Build HealthObject
+        glib::Object::builder()
+            .property("kind", kind)
+            .property("section", section)
+            .property("package", package)
+            .property("details", details)
+            .build()
+    }
+}
@@ -0,0 +1,418 @@
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+use gtk::{glib, gio, gdk};
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use glib::clone;
+use gdk::{Key, ModifierType};
+
+use crate::{
+    health_object::{HealthObject, HealthKind},
+    pkg_object::PkgObject,
+    pkg_data::PkgFlags
+};
+
+//------------------------------------------------------------------------------
+// STRUCT: HealthPkg
+//------------------------------------------------------------------------------
+struct HealthPkg {
+    name: String,
+    version: String,
+    is_explicit: bool,
+    depends: Vec<String>,
+    optdepends: Vec<String>,
+    provides: Vec<String>,
+    conflicts: Vec<String>
+}
+
+impl HealthPkg {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    fn new(pkg: &PkgObject) -> Self {
+        Self {
+            name: pkg.name(),
+            version: pkg.version(),
+            is_explicit: pkg.flags().intersects(PkgFlags::EXPLICIT),
+            depends: pkg.depends().to_vec(),
+            optdepends: pkg.optdepends().to_vec(),
+            provides: pkg.provides().to_vec(),
+            conflicts: pkg.conflicts().to_vec()
+        }
+    }
+
+    //---------------------------------------
+    // Satisfies function
+    //---------------------------------------
+    fn satisfies(&self, search_term: &str) -> bool {
+        PkgObject::satisfies_dep(&self.name, &self.version, &self.provides, search_term)
+    }
+}
+
+//------------------------------------------------------------------------------
+// MODULE: HealthWindow
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate, glib::Properties)]
+    #[properties(wrapper_type = super::HealthWindow)]
+    #[template(resource = "/com/github/PacView/ui/health_window.ui")]
+    pub struct HealthWindow {
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) view: TemplateChild<gtk::ListView>,
+        #[template_child]
+        pub(super) model: TemplateChild<gio::ListStore>,
+        #[template_child]
+        pub(super) section_sort_model: TemplateChild<gtk::SortListModel>,
+        #[template_child]
+        pub(super) selection: TemplateChild<gtk::NoSelection>,
+        #[template_child]
+        pub(super) section_sorter: TemplateChild<gtk::CustomSorter>,
+        #[template_child]
+        pub(super) optional_filter: TemplateChild<gtk::CustomFilter>,
+
+        #[template_child]
+        pub(super) footer_label: TemplateChild<gtk::Label>,
+
+        #[property(get, set)]
+        is_loaded: Cell<bool>,
+        #[property(get, set)]
+        show_optional: Cell<bool>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for HealthWindow {
+        const NAME: &'static str = "HealthWindow";
+        type Type = super::HealthWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            HealthObject::ensure_type();
+
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+
+            // Add key bindings
+            Self::bind_shortcuts(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for HealthWindow {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+            obj.setup_widgets();
+        }
+    }
+
+    impl WidgetImpl for HealthWindow {}
+    impl WindowImpl for HealthWindow {}
+    impl AdwWindowImpl for HealthWindow {}
+
+    impl HealthWindow {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Show optional property action
+            klass.install_property_action("health.show-optional", "show-optional");
+
+            // Copy action
+            klass.install_action("health.copy", None, |window, _, _| {
+                let mut section = String::new();
+                let mut output = String::from("## System Health\n|Package Name|Details|\n|---|---|\n");
+
+                for item in window.imp().selection.iter::<glib::Object>()
+                    .flatten()
+                    .filter_map(|item| item.downcast::<HealthObject>().ok()) {
+                        let item_section = item.section();
+
+                        if item_section != section {
+                            writeln!(output, "|**{item_section}**||").unwrap();
+
+                            section = item_section;
+                        }
+
+                        writeln!(output, "|{package}|{details}|",
+                            package=item.package(),
+                            details=item.details()
+                        )
+                        .unwrap();
+                    }
+
+                window.clipboard().set_text(&output);
+            });
+        }
+
+        //---------------------------------------
+        // Bind shortcuts
+        //---------------------------------------
+        fn bind_shortcuts(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Close window binding
+            klass.add_binding_action(Key::Escape, ModifierType::NO_MODIFIER_MASK, "window.close");
+
+            // Show optional key binding
+            klass.add_binding_action(Key::O, ModifierType::CONTROL_MASK, "health.show-optional");
+
+            // Copy key binding
+            klass.add_binding_action(Key::C, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "health.copy");
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: HealthWindow
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct HealthWindow(ObjectSubclass<imp::HealthWindow>)
+        @extends adw::Window, gtk::Window, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Native, gtk::Root, gtk::ShortcutManager;
+}
+
+impl HealthWindow {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        let imp = self.imp();
+
+        // Show optional property notify signal
+        self.connect_show_optional_notify(|window| {
+            window.imp().optional_filter.changed(gtk::FilterChange::Different);
+        });
+
+        // Section sort model items changed signal
+        imp.section_sort_model.connect_items_changed(clone!(
+            #[weak(rename_to = window)] self,
+            move |sort_model, _, _, _| {
+                let imp = window.imp();
+
+                let n_items = sort_model.n_items();
+
+                imp.stack.set_visible_child_name(
+                    if n_items == 0 { "empty" } else { "view" }
+                );
+
+                // Count problems of each kind in unfiltered model
+                let count = |kind: HealthKind| -> usize {
+                    imp.model.iter::<HealthObject>()
+                        .flatten()
+                        .filter(|item| item.kind() == kind)
+                        .count()
+                };
+
+                imp.footer_label.set_label(&format!("{} unsatisfied dependencies, {} conflicts, {} missing optional dependencies",
+                    count(HealthKind::Broken),
+                    count(HealthKind::Conflict),
+                    count(HealthKind::Optional)
+                ));
+
+                window.action_set_enabled("health.copy", n_items > 0);
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Setup widgets
+    //---------------------------------------
+    fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        // Set section sorter function (sort by kind, then by section)
+        imp.section_sorter.set_sort_func(|item_a, item_b| {
+            let item_a = item_a
+                .downcast_ref::<HealthObject>()
+                .expect("Failed to downcast to 'HealthObject'");
+
+            let item_b = item_b
+                .downcast_ref::<HealthObject>()
+                .expect("Failed to downcast to 'HealthObject'");
+
+            item_a.kind().cmp(&item_b.kind())
+                .then_with(|| item_a.section().to_lowercase().cmp(&item_b.section().to_lowercase()))
+                .into()
+        });
+
+        // Set optional filter function
+        imp.optional_filter.set_filter_func(clone!(
+            #[weak(rename_to = window)] self,
+            #[upgrade_or] false,
+            move |item| {
+                window.show_optional() || item
+                    .downcast_ref::<HealthObject>()
+                    .expect("Failed to downcast to 'HealthObject'")
+                    .kind() != HealthKind::Optional
+            }
+        ));
+
+        // Set initial focus on view
+        imp.view.grab_focus();
+    }
+
+    //---------------------------------------
+    // Populate window
+    //---------------------------------------
+    fn populate(&self, pkg_model: &gio::ListStore) {
+        let imp = self.imp();
+
+        let installed: Vec<HealthPkg> = pkg_model.iter::<PkgObject>()
+            .flatten()
+            .filter(|pkg| pkg.is_installed())
+            .map(|pkg| HealthPkg::new(&pkg))
+            .collect();
+
+        glib::spawn_future_local(clone!(
+            #[weak] imp,
+            async move {
+                let results = gio::spawn_blocking(move || Self::scan_packages(&installed))
+                    .await
+                    .unwrap_or_default();
+
+                let items: Vec<HealthObject> = results.iter()
+                    .map(|(kind, section, package, details)| HealthObject::new(*kind, section, package, details))
+                    .collect();
+
+                // Populate list view
+                imp.model.splice(0, imp.model.n_items(), &items);
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Scan packages function
+    //---------------------------------------
+    fn scan_packages(installed: &[HealthPkg]) -> Vec<(HealthKind, String, String, String)> {
+        // Index installed packages by name and provided names
+        let mut satisfiers: HashMap<&str, Vec<&HealthPkg>> = HashMap::new();
+
+        for pkg in installed {
+            satisfiers.entry(&pkg.name).or_default().push(pkg);
+
+            for provide in &pkg.provides {
+                let (name, _) = PkgObject::split_dependency(provide);
+
+                satisfiers.entry(name).or_default().push(pkg);
+            }
+        }
+
+        let has_satisfier = |dep: &str| {
+            let (name, _) = PkgObject::split_dependency(dep);
+
+            satisfiers.get(name)
+                .is_some_and(|pkgs| pkgs.iter().any(|pkg| pkg.satisfies(dep)))
+        };
+
+        let mut items: Vec<(HealthKind, String, String, String)> = vec![];
+
+        // Get dependencies without a local satisfier
+        for pkg in installed {
+            for dep in pkg.depends.iter().filter(|dep| !has_satisfier(dep)) {
+                items.push((HealthKind::Broken, String::from("Unsatisfied Dependencies"), pkg.name.clone(), format!("requires {dep}")));
+            }
+        }
+
+        // Get pairs of installed packages that conflict with each other
+        let mut conflict_pairs: HashSet<(&str, &str)> = HashSet::new();
+
+        for pkg in installed {
+            for conflict in &pkg.conflicts {
+                let (name, _) = PkgObject::split_dependency(conflict);
+
+                let others = satisfiers.get(name).into_iter()
+                    .flatten()
+                    .filter(|other| other.name != pkg.name && other.satisfies(conflict));
+
+                for other in others {
+                    let pair = if pkg.name < other.name { (&*pkg.name, &*other.name) } else { (&*other.name, &*pkg.name) };
+
+                    if conflict_pairs.insert(pair) {
+                        items.push((HealthKind::Conflict, String::from("Conflicting Packages"), pair.0.to_owned(), format!("conflicts with {}", pair.1)));
+                    }
+                }
+            }
+        }
+
+        // Get missing optional dependencies of explicit packages, grouped by feature
+        let mut optdeps: HashMap<(&str, &str), Vec<&str>> = HashMap::new();
+
+        for pkg in installed.iter().filter(|pkg| pkg.is_explicit) {
+            for optdep in &pkg.optdepends {
+                let (name, feature) = PkgObject::split_optdep(optdep);
+
+                if !has_satisfier(name) {
+                    let feature = feature.filter(|feature| !feature.is_empty())
+                        .unwrap_or("Other");
+
+                    optdeps.entry((feature, name))
+                        .or_default()
+                        .push(&pkg.name);
+                }
+            }
+        }
+
+        // Sort optional dependencies by package name
+        let mut optdeps: Vec<((&str, &str), Vec<&str>)> = optdeps.into_iter().collect();
+
+        optdeps.sort_unstable_by_key(|&((feature, name), _)| (name, feature));
+
+        items.extend(optdeps.into_iter()
+            .map(|((feature, name), pkgs)| {
+                (HealthKind::Optional, feature.to_owned(), name.to_owned(), format!("for {}", pkgs.join(", ")))
+            })
+        );
+
+        items
+    }
+
+    //---------------------------------------
+    // Show window
+    //---------------------------------------
+    pub fn show(&self, pkg_model: &gio::ListStore) {
+        self.present();
+
+        glib::idle_add_local_once(clone!(
+            #[weak(rename_to = window)] self,
+            #[weak] pkg_model,
+            move || {
+                if !window.is_loaded() {
+                    window.populate(&pkg_model);
+
+                    window.set_is_loaded(true);
+                }
+            }
+        ));
+    }
+}
+
+impl Default for HealthWindow {
+    //---------------------------------------
+    // Default constructor
+    //---------------------------------------
+    fn default() -> Self {
+        glib::Object::builder().build()
+    }
+}
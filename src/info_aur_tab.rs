@@ -0,0 +1,300 @@
+use std::cell::{Cell, RefCell};
+
+use gtk::subclass::prelude::*;
+use adw::prelude::*;
+use gtk::glib;
+use glib::clone;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    pkg_object::PkgObject,
+    aur_page::{AurPage, AurComment}
+};
+
+//------------------------------------------------------------------------------
+// MODULE: InfoAurTab
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate)]
+    #[template(resource = "/com/github/PacView/ui/info_aur_tab.ui")]
+    pub struct InfoAurTab {
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) error_status: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub(super) details_box: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub(super) pinned_box: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub(super) comments_box: TemplateChild<gtk::ListBox>,
+
+        pub(super) pkg: RefCell<Option<PkgObject>>,
+        pub(super) is_loaded: Cell<bool>,
+
+        pub(super) fetch_token: RefCell<Option<CancellationToken>>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for InfoAurTab {
+        const NAME: &'static str = "InfoAurTab";
+        type Type = super::InfoAurTab;
+        type ParentType = gtk::Box;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for InfoAurTab {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+        }
+    }
+    impl WidgetImpl for InfoAurTab {}
+    impl BoxImpl for InfoAurTab {}
+
+    impl InfoAurTab {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Refresh action
+            klass.install_action("info.aur-refresh", None, |tab, _, _| {
+                tab.load_page();
+            });
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: InfoAurTab
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct InfoAurTab(ObjectSubclass<imp::InfoAurTab>)
+        @extends gtk::Box, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Orientable;
+}
+
+impl InfoAurTab {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        // Map signal: only fetch AUR page when tab is visible
+        self.connect_map(|tab| {
+            if !tab.imp().is_loaded.get() {
+                tab.load_page();
+            }
+        });
+    }
+
+    //---------------------------------------
+    // Date to string helper function
+    //---------------------------------------
+    fn date_to_string(date: i64) -> Option<String> {
+        if date == 0 {
+            return None
+        }
+
+        glib::DateTime::from_unix_local(date)
+            .and_then(|datetime| datetime.format("%c"))
+            .ok()
+            .map(String::from)
+    }
+
+    //---------------------------------------
+    // Add details row helper function
+    //---------------------------------------
+    fn add_details_row(&self, title: &str, value: Option<String>) {
+        let row = adw::ActionRow::builder()
+            .title(title)
+            .subtitle(value.as_deref().unwrap_or("None"))
+            .use_markup(false)
+            .subtitle_selectable(true)
+            .css_classes(["property"])
+            .build();
+
+        self.imp().details_box.append(&row);
+    }
+
+    //---------------------------------------
+    // Comment row helper function
+    //---------------------------------------
+    fn comment_row(comment: &AurComment) -> adw::ActionRow {
+        adw::ActionRow::builder()
+            .title(format!("{} \u{2022} {}", comment.author(), comment.date()))
+            .subtitle(comment.content())
+            .use_markup(false)
+            .subtitle_selectable(true)
+            .css_classes(["property"])
+            .build()
+    }
+
+    //---------------------------------------
+    // Clear page function
+    //---------------------------------------
+    fn clear_page(&self) {
+        let imp = self.imp();
+
+        imp.details_box.remove_all();
+        imp.pinned_box.remove_all();
+        imp.comments_box.remove_all();
+    }
+
+    //---------------------------------------
+    // Display page function
+    //---------------------------------------
+    fn display_page(&self, page: &AurPage) {
+        let imp = self.imp();
+
+        self.clear_page();
+
+        // Add package metadata rows
+        self.add_details_row("Maintainer", page.maintainer().map(String::from)
+            .or_else(|| Some(String::from("Orphan"))));
+        self.add_details_row("Co-Maintainers", (!page.co_maintainers().is_empty())
+            .then(|| page.co_maintainers().join(", ")));
+        self.add_details_row("Submitter", page.submitter().map(String::from));
+        self.add_details_row("First Submitted", Self::date_to_string(page.first_submitted()));
+        self.add_details_row("Last Updated", Self::date_to_string(page.last_modified()));
+        self.add_details_row("Keywords", (!page.keywords().is_empty())
+            .then(|| page.keywords().join(" ")));
+
+        if let Some(out_of_date) = page.out_of_date() {
+            self.add_details_row("Out of Date", Self::date_to_string(out_of_date));
+            self.add_details_row("Flag Reason", page.flag_reason().map(String::from));
+        }
+
+        // Add comment rows
+        for comment in page.comments() {
+            let row = Self::comment_row(comment);
+
+            if comment.pinned() {
+                imp.pinned_box.append(&row);
+            } else {
+                imp.comments_box.append(&row);
+            }
+        }
+
+        imp.pinned_box.set_visible(page.comments().iter().any(AurComment::pinned));
+
+        imp.stack.set_visible_child_name("page");
+    }
+
+    //---------------------------------------
+    // Load page function
+    //---------------------------------------
+    fn load_page(&self) {
+        let imp = self.imp();
+
+        // Cancel ongoing fetch if any
+        if let Some(token) = imp.fetch_token.take() {
+            token.cancel();
+        }
+
+        imp.is_loaded.set(true);
+
+        let Some(pkg) = imp.pkg.borrow().clone() else {
+            return
+        };
+
+        if pkg.repository() != "aur" {
+            self.clear_page();
+
+            self.action_set_enabled("info.aur-refresh", false);
+
+            imp.stack.set_visible_child_name("empty");
+
+            return
+        }
+
+        self.action_set_enabled("info.aur-refresh", true);
+
+        imp.stack.set_visible_child_name("loading");
+
+        let token = CancellationToken::new();
+
+        imp.fetch_token.replace(Some(token.clone()));
+
+        glib::spawn_future_local(clone!(
+            #[weak(rename_to = tab)] self,
+            async move {
+                let result = AurPage::fetch(&AurPage::url(), &pkg.name(), pkg.base(), token.clone()).await;
+
+                // Ignore results of superseded fetches
+                if token.is_cancelled() {
+                    return
+                }
+
+                match result {
+                    Ok(page) => {
+                        tab.display_page(&page);
+                    },
+                    Err(error) => {
+                        let imp = tab.imp();
+
+                        tab.clear_page();
+
+                        imp.error_status.set_description(Some(&error.to_string()));
+
+                        imp.stack.set_visible_child_name("error");
+                    }
+                }
+
+                tab.imp().fetch_token.take();
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Pause view function
+    //---------------------------------------
+    pub fn pause_view(&self) {
+        let imp = self.imp();
+
+        if let Some(token) = imp.fetch_token.take() {
+            token.cancel();
+        }
+
+        self.clear_page();
+
+        imp.stack.set_visible_child_name("loading");
+    }
+
+    //---------------------------------------
+    // Update view function
+    //---------------------------------------
+    pub fn update_view(&self, pkg: &PkgObject) {
+        let imp = self.imp();
+
+        imp.pkg.replace(Some(pkg.clone()));
+        imp.is_loaded.set(false);
+
+        if self.is_mapped() {
+            self.load_page();
+        }
+    }
+}
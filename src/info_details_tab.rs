@@ -8,13 +8,15 @@ use gtk::prelude::*;
 use gtk::glib;
 use glib::{clone, RustClosure};
 
+use size::Size;
+
 use crate::{
     pkg_object::PkgObject,
     info_row::{PropID, PropType, ValueType, InfoRow},
     text_widget::{INSTALLED_LABEL, LINK_SPACER},
     source_window::SourceWindow,
     hash_window::HashWindow,
-    utils::Paths,
+    utils::PkgbuildSource,
 };
 
 //------------------------------------------------------------------------------
@@ -63,6 +65,7 @@ mod imp {
 
         pub(super) info_row_map: RefCell<HashMap<PropID, InfoRow>>,
         pub(super) selection_row: RefCell<Option<InfoRow>>,
+        pub(super) srcinfo_handler: RefCell<Option<(PkgObject, glib::SignalHandlerId)>>,
     }
 
     //---------------------------------------
@@ -178,22 +181,31 @@ impl InfoDetailsTab {
             (PropID::PackageUrl, PropType::Link),
             (PropID::Url, PropType::Link),
             (PropID::Groups, PropType::Text),
+            (PropID::PackageBase, PropType::LinkList),
             (PropID::Provides, PropType::Text),
             (PropID::Dependencies, PropType::LinkList),
+            (PropID::ArchDependencies, PropType::Text),
             (PropID::Optional, PropType::LinkList),
             (PropID::Make, PropType::LinkList),
+            (PropID::Check, PropType::LinkList),
             (PropID::RequiredBy, PropType::LinkList),
             (PropID::OptionalFor, PropType::LinkList),
             (PropID::ConflictsWith, PropType::LinkList),
             (PropID::Replaces, PropType::LinkList),
             (PropID::Architecture, PropType::Text),
+            (PropID::Epoch, PropType::Text),
             (PropID::Licenses, PropType::Text),
             (PropID::Packager, PropType::Packager),
+            (PropID::AurMaintainer, PropType::Text),
+            (PropID::Origin, PropType::Text),
             (PropID::BuildDate, PropType::Text),
             (PropID::InstallDate, PropType::Text),
             (PropID::DownloadSize, PropType::Text),
+            (PropID::DiskUsage, PropType::Text),
             (PropID::InstallScript, PropType::Text),
-            (PropID::Validation, PropType::Text)
+            (PropID::Validation, PropType::Text),
+            (PropID::Sources, PropType::Text),
+            (PropID::PgpKeys, PropType::Text)
         ] {
             let imp = self.imp();
 
@@ -245,12 +257,13 @@ impl InfoDetailsTab {
         if !pkg.optdepends().is_empty() && pkg.is_installed() {
             pkg.optdepends().iter()
                 .map(|dep| {
-                    if dep.split_once([':'])
-                        .is_some_and(|(name, _)| PkgObject::has_local_satisfier(name)) {
-                            dep.to_owned() + INSTALLED_LABEL
-                        } else {
-                            dep.to_owned()
-                        }
+                    let (name, _) = PkgObject::split_optdep(dep);
+
+                    if PkgObject::has_local_satisfier(name) {
+                        dep.to_owned() + INSTALLED_LABEL
+                    } else {
+                        dep.to_owned()
+                    }
                 })
                 .collect()
         } else {
@@ -277,18 +290,27 @@ impl InfoDetailsTab {
         // Groups
         self.set_info_row(PropID::Groups, ValueType::VecOptJoin(pkg.groups()));
 
+        // Package base split packages
+        self.set_info_row(PropID::PackageBase, ValueType::VecOpt(pkg.base_siblings()));
+
         // Provides
         self.set_info_row(PropID::Provides, ValueType::VecOpt(pkg.provides()));
 
         // Depends
         self.set_info_row(PropID::Dependencies, ValueType::Vec(pkg.depends()));
 
+        // Architecture specific depends (updated asynchronously)
+        self.set_info_row(PropID::ArchDependencies, ValueType::VecOpt(&[]));
+
         // Optdepends
         self.set_info_row(PropID::Optional, ValueType::VecOpt(&Self::installed_optdeps(pkg)));
 
         // Makedepends
         self.set_info_row(PropID::Make, ValueType::VecOpt(pkg.makedepends()));
 
+        // Checkdepends
+        self.set_info_row(PropID::Check, ValueType::VecOpt(pkg.checkdepends()));
+
         // Required by
         self.set_info_row(PropID::RequiredBy, ValueType::Vec(pkg.required_by()));
 
@@ -304,12 +326,21 @@ impl InfoDetailsTab {
         // Architecture
         self.set_info_row(PropID::Architecture, ValueType::StrOpt(pkg.architecture()));
 
+        // Epoch (updated asynchronously)
+        self.set_info_row(PropID::Epoch, ValueType::StrOpt(None));
+
         // Licenses
         self.set_info_row(PropID::Licenses, ValueType::VecOptJoin(pkg.licenses()));
 
         // Packager
         self.set_info_row(PropID::Packager, ValueType::StrOpt(pkg.packager()));
 
+        // AUR maintainer
+        self.set_info_row(PropID::AurMaintainer, ValueType::StrOpt(pkg.aur_maintainer()));
+
+        // Origin
+        self.set_info_row(PropID::Origin, ValueType::StrOpt(pkg.origin().as_deref()));
+
         // Build date
         self.set_info_row(PropID::BuildDate, ValueType::StrOpt(pkg.build_date_string().as_deref()));
 
@@ -319,11 +350,20 @@ impl InfoDetailsTab {
         // Download size
         self.set_info_row(PropID::DownloadSize, ValueType::StrOptNum(&pkg.download_size_string(), pkg.download_size()));
 
+        // Disk usage (updated asynchronously)
+        self.set_info_row(PropID::DiskUsage, ValueType::StrOpt(None));
+
         // Has script
         self.set_info_row(PropID::InstallScript, ValueType::StrOpt(pkg.has_script()));
 
         // Validation
         self.set_info_row(PropID::Validation, ValueType::Str(&pkg.validation().to_string()));
+
+        // Sources (updated asynchronously)
+        self.set_info_row(PropID::Sources, ValueType::VecOpt(&[]));
+
+        // PGP keys (updated asynchronously)
+        self.set_info_row(PropID::PgpKeys, ValueType::VecOpt(&[]));
     }
 
     //---------------------------------------
@@ -345,6 +385,8 @@ impl InfoDetailsTab {
         imp.repo_label.set_label(&pkg.repository());
         imp.version_label.set_label(&pkg.version());
         imp.size_label.set_label(&pkg.install_size_string());
+        imp.size_label.set_tooltip_text(None);
+        imp.size_label.set_css_classes(&["tag", "accent"]);
 
         if let Some(update) = pkg.update_version() {
             imp.update_label.set_visible(true);
@@ -354,11 +396,128 @@ impl InfoDetailsTab {
         }
 
         // Update button states
-        imp.pkgbuild_button.set_visible(Paths::paru().is_ok());
+        imp.pkgbuild_button.set_visible(PkgbuildSource::is_available(&pkg.repository(), pkg.base())
+            || (pkg.is_installed() && pkg.has_script().is_some()));
 
         imp.hashes_button.set_visible(pkg.validation().is_valid());
     }
 
+    //---------------------------------------
+    // Update disk usage function
+    //---------------------------------------
+    fn update_disk_usage(&self, pkg: &PkgObject) {
+        glib::spawn_future_local(clone!(
+            #[weak(rename_to = tab)] self,
+            #[weak] pkg,
+            async move {
+                let Some(usage) = pkg.disk_usage_future().await else {
+                    return
+                };
+
+                // Return if package has changed
+                if tab.pkg().as_ref() != Some(&pkg) {
+                    return
+                }
+
+                let imp = tab.imp();
+
+                let declared_size = pkg.install_size();
+                let disk_size = Size::from_bytes(usage.total_size()).to_string();
+
+                imp.size_label.set_label(&format!("{} ({disk_size} on disk)", pkg.install_size_string()));
+
+                let mut value = format!("{} in package files", Size::from_bytes(usage.owned_size()));
+
+                if usage.untracked_files() > 0 {
+                    write!(value, ", {} in {} untracked file{}",
+                        Size::from_bytes(usage.untracked_size()),
+                        usage.untracked_files(),
+                        if usage.untracked_files() == 1 { "" } else { "s" }
+                    )
+                    .unwrap();
+                }
+
+                if usage.is_discrepant(declared_size) {
+                    let delta = usage.total_size() - declared_size;
+
+                    let warning = format!("{}{} compared to installed size",
+                        if delta > 0 { "+" } else { "-" },
+                        Size::from_bytes(delta.abs())
+                    );
+
+                    write!(value, " ({warning})").unwrap();
+
+                    imp.size_label.set_css_classes(&["tag", "warning"]);
+                    imp.size_label.set_tooltip_text(Some(&warning));
+                }
+
+                tab.set_info_row(PropID::DiskUsage, ValueType::Str(&value));
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Update srcinfo function
+    //---------------------------------------
+    fn update_srcinfo(&self, pkg: &PkgObject) {
+        let imp = self.imp();
+
+        // Disconnect handler from previous package
+        if let Some((old_pkg, handler)) = imp.srcinfo_handler.take() {
+            old_pkg.disconnect(handler);
+        }
+
+        if pkg.has_srcinfo() {
+            self.set_srcinfo_rows(pkg);
+        } else {
+            // .SRCINFO is only downloaded when package source is loaded
+            let handler = pkg.connect_has_srcinfo_notify(clone!(
+                #[weak(rename_to = tab)] self,
+                move |pkg| {
+                    if tab.pkg().as_ref() == Some(pkg) {
+                        tab.set_srcinfo_rows(pkg);
+                    }
+                }
+            ));
+
+            imp.srcinfo_handler.replace(Some((pkg.clone(), handler)));
+        }
+    }
+
+    //---------------------------------------
+    // Set srcinfo rows function
+    //---------------------------------------
+    fn set_srcinfo_rows(&self, pkg: &PkgObject) {
+        let Some(srcinfo) = pkg.srcinfo() else {
+            return
+        };
+
+        let name = pkg.name();
+
+        self.set_info_row(PropID::ArchDependencies, ValueType::VecOpt(&srcinfo.arch_depends(&name)));
+        self.set_info_row(PropID::Epoch, ValueType::StrOpt(srcinfo.epoch.as_deref()));
+        self.set_info_row(PropID::Sources, ValueType::VecOpt(&srcinfo.sources()));
+        self.set_info_row(PropID::PgpKeys, ValueType::VecOpt(&srcinfo.validpgpkeys));
+
+        // Fill in split packages and checkdepends missing from databases
+        if pkg.base_siblings().is_empty() {
+            let split_packages: Vec<String> = srcinfo.split_packages().into_iter()
+                .filter(|split| *split != name)
+                .collect();
+
+            self.set_info_row(PropID::PackageBase, ValueType::VecOpt(&split_packages));
+        }
+
+        if pkg.checkdepends().is_empty() {
+            self.set_info_row(PropID::Check, ValueType::VecOpt(&srcinfo.checkdepends()));
+        }
+
+        // Fill in architecture for packages without one (AUR)
+        if pkg.architecture().is_none() {
+            self.set_info_row(PropID::Architecture, ValueType::VecOptJoin(&srcinfo.package_arch(&name)));
+        }
+    }
+
     //---------------------------------------
     // Public update function
     //---------------------------------------
@@ -367,5 +526,8 @@ impl InfoDetailsTab {
         self.update_details(pkg, count_label);
 
         self.set_pkg(Some(pkg));
+
+        self.update_disk_usage(pkg);
+        self.update_srcinfo(pkg);
     }
 }
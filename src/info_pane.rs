@@ -12,8 +12,10 @@ use crate::{
     info_details_tab::InfoDetailsTab,
     info_files_tab::InfoFilesTab,
     info_log_tab::InfoLogTab,
+    info_aur_tab::InfoAurTab,
     history_list::HistoryList,
     pkg_object::PkgObject,
+    provider_dialog::ProviderDialog,
     text_widget::TextWidget
 };
 
@@ -48,6 +50,8 @@ mod imp {
         pub(super) files_tab: TemplateChild<InfoFilesTab>,
         #[template_child]
         pub(super) log_tab: TemplateChild<InfoLogTab>,
+        #[template_child]
+        pub(super) aur_tab: TemplateChild<InfoAurTab>,
 
         #[property(get = Self::pkg, set = Self::set_pkg, nullable)]
         pkg: PhantomData<Option<PkgObject>>,
@@ -214,6 +218,24 @@ impl InfoPane {
         let pkg_model = self.package_view().pkg_model();
         let aur_model = self.package_view().aur_model();
 
+        // If link is a virtual dependency with several providers, let user choose one
+        let providers = PkgObject::find_providers(&pkg_link, &[&pkg_model, &aur_model]);
+
+        if providers.len() > 1 && providers.iter().all(|pkg| pkg.name() != pkg_name) {
+            let dialog = ProviderDialog::new(&pkg_link, &providers);
+
+            dialog.connect_closure("provider-selected", false, closure_local!(
+                #[weak(rename_to = pane)] self,
+                move |_: ProviderDialog, pkg: PkgObject| {
+                    pane.display_link_pkg(pkg);
+                }
+            ));
+
+            dialog.present(Some(self));
+
+            return
+        }
+
         let new_pkg = PkgObject::find_satisfier(&pkg_link, &pkg_model)
             .or_else(|| {
                 aur_model.iter::<PkgObject>()
@@ -226,17 +248,24 @@ impl InfoPane {
                     })
             });
 
-        // If link package found
+        // If link package found, display it
         if let Some(pkg) = new_pkg {
-            let history = self.imp().pkg_history.borrow();
+            self.display_link_pkg(pkg);
+        }
+    }
 
-            // If link package is in history, select it
-            // Otherwise append it after selected history package
-            history.select_or_append(pkg);
+    //---------------------------------------
+    // Display link package function
+    //---------------------------------------
+    fn display_link_pkg(&self, pkg: PkgObject) {
+        let history = self.imp().pkg_history.borrow();
 
-            // Display link package
-            self.update_display();
-        }
+        // If link package is in history, select it
+        // Otherwise append it after selected history package
+        history.select_or_append(pkg);
+
+        // Display link package
+        self.update_display();
     }
 
     //---------------------------------------
@@ -262,9 +291,10 @@ impl InfoPane {
             if let Some(delay_id) = imp.update_delay_id.take() {
                 delay_id.remove();
 
-                // Clear files/log tabs
+                // Clear files/log/AUR tabs
                 imp.files_tab.pause_view();
                 imp.log_tab.pause_view();
+                imp.aur_tab.pause_view();
             }
 
             // Start delay timer
@@ -273,9 +303,10 @@ impl InfoPane {
                 clone!(
                     #[weak] imp,
                     move || {
-                        // Populate files/log tabs
+                        // Populate files/log/AUR tabs
                         imp.files_tab.update_view(&pkg);
                         imp.log_tab.update_view(&pkg);
+                        imp.aur_tab.update_view(&pkg);
 
                         imp.update_delay_id.take();
                     }
@@ -29,12 +29,18 @@ pub enum PropID {
     Url,
     #[strum(serialize = "Groups")]
     Groups,
+    #[strum(serialize = "Package Base")]
+    PackageBase,
     #[strum(serialize = "Dependencies")]
     Dependencies,
+    #[strum(serialize = "Arch Dependencies")]
+    ArchDependencies,
     #[strum(serialize = "Optional")]
     Optional,
     #[strum(serialize = "Make")]
     Make,
+    #[strum(serialize = "Check")]
+    Check,
     #[strum(serialize = "Required By")]
     RequiredBy,
     #[strum(serialize = "Optional For")]
@@ -49,18 +55,30 @@ pub enum PropID {
     Licenses,
     #[strum(serialize = "Architecture")]
     Architecture,
+    #[strum(serialize = "Epoch")]
+    Epoch,
     #[strum(serialize = "Packager")]
     Packager,
+    #[strum(serialize = "AUR Maintainer")]
+    AurMaintainer,
+    #[strum(serialize = "Origin")]
+    Origin,
     #[strum(serialize = "Build Date")]
     BuildDate,
     #[strum(serialize = "Install Date")]
     InstallDate,
     #[strum(serialize = "Download Size")]
     DownloadSize,
+    #[strum(serialize = "Disk Usage")]
+    DiskUsage,
     #[strum(serialize = "Install Script")]
     InstallScript,
     #[strum(serialize = "Validation")]
     Validation,
+    #[strum(serialize = "Sources")]
+    Sources,
+    #[strum(serialize = "PGP Keys")]
+    PgpKeys,
 }
 
 //------------------------------------------------------------------------------
@@ -0,0 +1,516 @@
+use std::io;
+
+use crate::utils::{Pacman, Paths, TokioUtils};
+
+//------------------------------------------------------------------------------
+// STRUCT: PgpSignature
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Clone)]
+pub struct PgpSignature {
+    version: u8,
+    key_id: Option<String>,
+    fingerprint: Option<String>,
+    created: Option<i64>,
+    pubkey_algorithm: u8,
+    hash_algorithm: u8
+}
+
+impl PgpSignature {
+    //---------------------------------------
+    // Base64 decode helper function
+    //---------------------------------------
+    fn decode_base64(text: &str) -> Option<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+
+        let mut buffer = 0u32;
+        let mut bits = 0;
+
+        for c in text.bytes().filter(|c| !c.is_ascii_whitespace() && *c != b'=') {
+            let value = match c {
+                b'A'..=b'Z' => c - b'A',
+                b'a'..=b'z' => c - b'a' + 26,
+                b'0'..=b'9' => c - b'0' + 52,
+                b'+' => 62,
+                b'/' => 63,
+                _ => return None
+            };
+
+            buffer = (buffer << 6) | u32::from(value);
+            bits += 6;
+
+            if bits >= 8 {
+                bits -= 8;
+                bytes.push((buffer >> bits) as u8);
+            }
+        }
+
+        Some(bytes)
+    }
+
+    //---------------------------------------
+    // Hex string helper function
+    //---------------------------------------
+    fn to_hex(bytes: &[u8]) -> String {
+        bytes.iter()
+            .map(|byte| format!("{byte:02X}"))
+            .collect()
+    }
+
+    //---------------------------------------
+    // Packet body helper function
+    //---------------------------------------
+    fn packet_body(data: &[u8]) -> Option<&[u8]> {
+        let header = *data.first()?;
+
+        if header & 0x80 == 0 {
+            return None
+        }
+
+        let (tag, start, len): (u8, usize, usize) = if header & 0x40 == 0 {
+            // Old format packet
+            let tag = (header >> 2) & 0x0F;
+
+            match header & 0x03 {
+                0 => (tag, 2, usize::from(*data.get(1)?)),
+                1 => (tag, 3, usize::from(u16::from_be_bytes(data.get(1..3)?.try_into().ok()?))),
+                2 => (tag, 5, u32::from_be_bytes(data.get(1..5)?.try_into().ok()?) as usize),
+                _ => (tag, 1, data.len() - 1)
+            }
+        } else {
+            // New format packet
+            let tag = header & 0x3F;
+            let first = usize::from(*data.get(1)?);
+
+            match first {
+                0..192 => (tag, 2, first),
+                192..224 => (tag, 3, ((first - 192) << 8) + usize::from(*data.get(2)?) + 192),
+                255 => (tag, 6, u32::from_be_bytes(data.get(2..6)?.try_into().ok()?) as usize),
+                _ => return None
+            }
+        };
+
+        // Signature packet tag
+        if tag != 2 {
+            return None
+        }
+
+        data.get(start..start.checked_add(len)?)
+    }
+
+    //---------------------------------------
+    // Parse subpackets helper function
+    //---------------------------------------
+    fn parse_subpackets(&mut self, mut data: &[u8]) {
+        while let Some(&first) = data.first() {
+            let first = usize::from(first);
+
+            let (start, len): (usize, usize) = match first {
+                0..192 => (1, first),
+                192..255 => match data.get(1) {
+                    Some(&second) => (2, ((first - 192) << 8) + usize::from(second) + 192),
+                    None => return
+                },
+                _ => match data.get(1..5).and_then(|bytes| bytes.try_into().ok()) {
+                    Some(bytes) => (5, u32::from_be_bytes(bytes) as usize),
+                    None => return
+                }
+            };
+
+            let Some(end) = start.checked_add(len).filter(|&end| end <= data.len()) else {
+                return
+            };
+
+            let subpacket = &data[start..end];
+
+            if let Some((&kind, body)) = subpacket.split_first() {
+                match kind & 0x7F {
+                    // Signature creation time
+                    2 => if let Ok(bytes) = body.try_into() {
+                        self.created = Some(i64::from(u32::from_be_bytes(bytes)));
+                    },
+                    // Issuer key ID
+                    16 if body.len() == 8 => {
+                        self.key_id = Some(Self::to_hex(body));
+                    },
+                    // Issuer fingerprint
+                    33 if body.len() > 1 => {
+                        self.fingerprint = Some(Self::to_hex(&body[1..]));
+                    },
+                    _ => {}
+                }
+            }
+
+            data = &data[end..];
+        }
+    }
+
+    //---------------------------------------
+    // From base64 function
+    //---------------------------------------
+    pub fn from_base64(base64_sig: &str) -> Option<Self> {
+        Self::from_bytes(&Self::decode_base64(base64_sig)?)
+    }
+
+    //---------------------------------------
+    // From bytes function
+    //---------------------------------------
+    fn from_bytes(data: &[u8]) -> Option<Self> {
+        let body = Self::packet_body(data)?;
+
+        let mut sig = Self {
+            version: *body.first()?,
+            ..Self::default()
+        };
+
+        match sig.version {
+            3 => {
+                sig.created = Some(i64::from(u32::from_be_bytes(body.get(3..7)?.try_into().ok()?)));
+                sig.key_id = Some(Self::to_hex(body.get(7..15)?));
+                sig.pubkey_algorithm = *body.get(15)?;
+                sig.hash_algorithm = *body.get(16)?;
+            },
+            4..=6 => {
+                sig.pubkey_algorithm = *body.get(2)?;
+                sig.hash_algorithm = *body.get(3)?;
+
+                // Version 6 signatures use 4-byte subpacket area lengths
+                let count_len = if sig.version == 6 { 4 } else { 2 };
+
+                let read_area = |offset: usize| -> Option<&[u8]> {
+                    let start = offset.checked_add(count_len)?;
+
+                    let len = body.get(offset..start)?.iter()
+                        .try_fold(0usize, |len, &byte| Some((len << 8) | usize::from(byte)))?;
+
+                    body.get(start..start.checked_add(len)?)
+                };
+
+                let hashed = read_area(4)?;
+                let unhashed = read_area(4 + count_len + hashed.len())?;
+
+                sig.parse_subpackets(hashed);
+                sig.parse_subpackets(unhashed);
+
+                // Derive key ID from version 4 fingerprint
+                if sig.key_id.is_none() && let Some(fingerprint) = &sig.fingerprint && fingerprint.len() == 40 {
+                    sig.key_id = Some(fingerprint[24..].to_owned());
+                }
+            },
+            _ => return None
+        }
+
+        (sig.key_id.is_some() || sig.fingerprint.is_some()).then_some(sig)
+    }
+
+    //---------------------------------------
+    // Public getters
+    //---------------------------------------
+    pub fn key_id(&self) -> Option<&str> {
+        self.key_id.as_deref()
+    }
+
+    pub fn fingerprint(&self) -> Option<&str> {
+        self.fingerprint.as_deref()
+    }
+
+    pub fn created(&self) -> Option<i64> {
+        self.created
+    }
+
+    pub fn issuer(&self) -> Option<&str> {
+        self.fingerprint().or_else(|| self.key_id())
+    }
+
+    pub fn algorithm(&self) -> String {
+        let pubkey = match self.pubkey_algorithm {
+            1..=3 => "RSA",
+            16 => "ElGamal",
+            17 => "DSA",
+            18 => "ECDH",
+            19 => "ECDSA",
+            22 => "EdDSA",
+            27 => "Ed25519",
+            28 => "Ed448",
+            _ => "Unknown"
+        };
+
+        let hash = match self.hash_algorithm {
+            1 => "MD5",
+            2 => "SHA1",
+            3 => "RIPEMD160",
+            8 => "SHA256",
+            9 => "SHA384",
+            10 => "SHA512",
+            11 => "SHA224",
+            12 => "SHA3-256",
+            14 => "SHA3-512",
+            _ => "Unknown"
+        };
+
+        format!("{pubkey} / {hash} (version {})", self.version)
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: KeyringKey
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Clone)]
+pub struct KeyringKey {
+    key_id: String,
+    fingerprint: String,
+    validity: char,
+    owner_trust: char,
+    is_disabled: bool,
+    expires: Option<i64>,
+    uids: Vec<String>,
+    signers: Vec<String>
+}
+
+impl KeyringKey {
+    //---------------------------------------
+    // Parse colon listing function
+    //---------------------------------------
+    fn parse_colons(listing: &str) -> Vec<Self> {
+        let mut keys: Vec<Self> = vec![];
+
+        let mut in_uid = false;
+
+        let timestamp = |field: Option<&&str>| field.and_then(|value| value.parse::<i64>().ok())
+            .filter(|&value| value != 0);
+
+        let flag = |field: Option<&&str>| field.and_then(|value| value.chars().next()).unwrap_or('-');
+
+        for line in listing.lines() {
+            let fields: Vec<&str> = line.split(':').collect();
+
+            match fields.first().copied() {
+                Some("pub") => {
+                    in_uid = false;
+
+                    keys.push(Self {
+                        key_id: fields.get(4).copied().unwrap_or_default().to_owned(),
+                        validity: flag(fields.get(1)),
+                        owner_trust: flag(fields.get(8)),
+                        is_disabled: fields.get(11).is_some_and(|caps| caps.contains('D')),
+                        expires: timestamp(fields.get(6)),
+                        ..Self::default()
+                    });
+                },
+                Some("fpr") => {
+                    // Only use primary key fingerprint
+                    if let Some(key) = keys.last_mut() && key.fingerprint.is_empty() {
+                        key.fingerprint = fields.get(9).copied().unwrap_or_default().to_owned();
+                    }
+                },
+                Some("uid") => {
+                    in_uid = true;
+
+                    if let Some(key) = keys.last_mut() && let Some(uid) = fields.get(9) {
+                        key.uids.push(uid.replace("\\x3a", ":"));
+                    }
+                },
+                Some("sig") => {
+                    // Only use user ID certifications
+                    if in_uid && let Some(key) = keys.last_mut() && let Some(&signer) = fields.get(4)
+                        && signer != key.key_id && !key.signers.iter().any(|s| s == signer) {
+                            key.signers.push(signer.to_owned());
+                        }
+                },
+                Some("sub") => {
+                    in_uid = false;
+                },
+                _ => {}
+            }
+        }
+
+        keys
+    }
+
+    //---------------------------------------
+    // List keys async function
+    //---------------------------------------
+    pub async fn list(search_terms: &[&str]) -> io::Result<Vec<Self>> {
+        let gpg_path = Paths::gpg().as_ref().map_err(io::Error::other)?;
+
+        let gpg_dir = &Pacman::config().gpg_dir;
+
+        let mut args = vec![
+            "--homedir", gpg_dir.as_str(),
+            "--batch", "--no-permission-warning", "--lock-never",
+            "--with-colons", "--fixed-list-mode", "--list-sigs"
+        ];
+
+        args.extend_from_slice(search_terms);
+
+        let (code, stdout) = TokioUtils::run(gpg_path, args, None).await?;
+
+        // Exit code 2 is returned when no keys match the search terms
+        match code {
+            Some(0 | 2) => Ok(Self::parse_colons(&stdout)),
+            Some(code) => Err(io::Error::other(format!("gpg exited with code {code}"))),
+            None => Err(io::Error::other("gpg was terminated"))
+        }
+    }
+
+    //---------------------------------------
+    // Find key async function
+    //---------------------------------------
+    pub async fn find(search_term: &str) -> io::Result<Option<Self>> {
+        Ok(Self::list(&[search_term]).await?.into_iter().next())
+    }
+
+    //---------------------------------------
+    // Public getters
+    //---------------------------------------
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    pub fn expires(&self) -> Option<i64> {
+        self.expires
+    }
+
+    pub fn uids(&self) -> &[String] {
+        &self.uids
+    }
+
+    pub fn owner(&self) -> &str {
+        self.uids.first().map_or("Unknown", String::as_str)
+    }
+
+    pub fn signers(&self) -> &[String] {
+        &self.signers
+    }
+
+    pub fn is_master(&self) -> bool {
+        matches!(self.owner_trust, 'm' | 'f' | 'u')
+    }
+
+    pub fn is_trusted(&self) -> bool {
+        !self.is_disabled && matches!(self.validity, 'f' | 'u')
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.validity == 'r'
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.validity == 'e'
+    }
+
+    pub fn trust(&self) -> &'static str {
+        if self.is_disabled {
+            return "Disabled"
+        }
+
+        match self.validity {
+            'u' => "Ultimate",
+            'f' => "Full",
+            'm' => "Marginal",
+            'n' => "Never",
+            'r' => "Revoked",
+            'e' => "Expired",
+            'd' => "Disabled",
+            'i' => "Invalid",
+            _ => "Unknown"
+        }
+    }
+
+    //---------------------------------------
+    // User ID emails function
+    //---------------------------------------
+    pub fn emails(&self) -> Vec<&str> {
+        self.uids.iter()
+            .filter_map(|uid| Self::uid_email(uid))
+            .collect()
+    }
+
+    //---------------------------------------
+    // User ID email helper function
+    //---------------------------------------
+    pub fn uid_email(uid: &str) -> Option<&str> {
+        uid.rsplit_once('<')
+            .and_then(|(_, email)| email.split_once('>'))
+            .map(|(email, _)| email)
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Detached Ed25519 signature created with gpg --detach-sign
+    const SIGNATURE: &str = "\
+iHUEABYIAB0WIQQY4xYlexgHUnXNzP2xI23dSddV6QUCatU04gAKCRCxI23dSddV6b5NAQD/aszmVNfWWu5U\
+Z1OkEkMIsRGVCYo2ek4iW3dYsGLlqwD/f+vOE9NiaXbR6EVzAPrdajtdWXRUNeKySIt+kw1Q3gI=";
+
+    #[test]
+    fn parse_detached_signature() {
+        let sig = PgpSignature::from_base64(SIGNATURE).expect("Failed to parse signature");
+
+        assert_eq!(sig.fingerprint(), Some("18E316257B18075275CDCCFDB1236DDD49D755E9"));
+        assert_eq!(sig.key_id(), Some("B1236DDD49D755E9"));
+        assert_eq!(sig.issuer(), Some("18E316257B18075275CDCCFDB1236DDD49D755E9"));
+        assert_eq!(sig.created(), Some(1_792_357_602));
+        assert_eq!(sig.algorithm(), "EdDSA / SHA256 (version 4)");
+    }
+
+    #[test]
+    fn decode_base64_ignores_whitespace_and_padding() {
+        assert_eq!(PgpSignature::decode_base64("TWFu").as_deref(), Some(&b"Man"[..]));
+        assert_eq!(PgpSignature::decode_base64("TW\nE=").as_deref(), Some(&b"Ma"[..]));
+        assert_eq!(PgpSignature::decode_base64("TQ==").as_deref(), Some(&b"M"[..]));
+        assert!(PgpSignature::decode_base64("TW*u").is_none());
+    }
+
+    #[test]
+    fn truncated_signature_returns_none() {
+        let data = PgpSignature::decode_base64(SIGNATURE).expect("Failed to decode signature");
+
+        for len in 0..data.len() {
+            assert!(PgpSignature::from_bytes(&data[..len]).is_none(), "Truncated to {len} bytes");
+        }
+
+        assert!(PgpSignature::from_base64(&SIGNATURE[..40]).is_none());
+    }
+
+    #[test]
+    fn garbage_input_does_not_panic() {
+        assert!(PgpSignature::from_base64("").is_none());
+        assert!(PgpSignature::from_base64("not base64!").is_none());
+        assert!(PgpSignature::from_base64("AAAAAAAA").is_none());
+
+        // Non-signature packet tag
+        assert!(PgpSignature::from_bytes(&[0x99, 0x01, 0x04]).is_none());
+
+        // Declared lengths larger than data
+        assert!(PgpSignature::from_bytes(&[0x89, 0xFF, 0xFF]).is_none());
+        assert!(PgpSignature::from_bytes(&[0xC2, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]).is_none());
+        assert!(PgpSignature::from_bytes(&[0x88, 0x06, 0x04, 0x00, 0x16, 0x08, 0xFF, 0xFF]).is_none());
+
+        let mut sig = PgpSignature::default();
+        sig.parse_subpackets(&[0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x02]);
+        sig.parse_subpackets(&[0xC0]);
+        sig.parse_subpackets(&[0x05, 0x02, 0x01]);
+        assert!(sig.created().is_none());
+
+        // Flip every byte of a valid signature
+        let data = PgpSignature::decode_base64(SIGNATURE).expect("Failed to decode signature");
+
+        for index in 0..data.len() {
+            for value in [0x00, 0x7F, 0xC0, 0xFF] {
+                let mut corrupt = data.clone();
+                corrupt[index] = value;
+
+                let _ = PgpSignature::from_bytes(&corrupt);
+            }
+        }
+    }
+}
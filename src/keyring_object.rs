@@ -0,0 +1,91 @@
+use std::cell::{Cell, RefCell};
+
+use gtk::glib;
+use gtk::subclass::prelude::*;
+use gtk::prelude::ObjectExt;
+
+use crate::keyring::KeyringKey;
+
+//------------------------------------------------------------------------------
+// MODULE: KeyringObject
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::KeyringObject)]
+    pub struct KeyringObject {
+        #[property(get, set, construct_only)]
+        uid: RefCell<String>,
+        #[property(get, set, construct_only)]
+        other_uids: RefCell<String>,
+        #[property(get, set, construct_only)]
+        fingerprint: RefCell<String>,
+        #[property(get, set, construct_only)]
+        status: RefCell<String>,
+        #[property(get, set, construct_only)]
+        status_css_classes: RefCell<Vec<String>>,
+        #[property(get, set, construct_only)]
+        expires: RefCell<String>,
+        #[property(get, set, construct_only)]
+        master_sigs: Cell<u32>,
+        #[property(get, set, construct_only)]
+        packages: RefCell<String>,
+        #[property(get, set, construct_only)]
+        n_packages: Cell<u32>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for KeyringObject {
+        const NAME: &'static str = "KeyringObject";
+        type Type = super::KeyringObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for KeyringObject {}
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: KeyringObject
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct KeyringObject(ObjectSubclass<imp::KeyringObject>);
+}
+
+impl KeyringObject {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    pub fn new(key: &KeyringKey, master_sigs: u32, packages: &[String]) -> Self {
+        let status_css_classes = if key.is_revoked() || key.is_expired() {
+            ["tag", "error"]
+        } else if key.is_trusted() {
+            ["tag", "success"]
+        } else {
+            ["tag", "warning"]
+        };
+
+        let expires = key.expires()
+            .and_then(|date| glib::DateTime::from_unix_local(date).ok())
+            .and_then(|datetime| datetime.format("%x").ok())
+            .map_or_else(|| String::from("Never"), String::from);
+
+        glib::Object::builder()
+            .property("uid", key.owner())
+            .property("other-uids", key.uids().get(1..).unwrap_or_default().join("\n"))
+            .property("fingerprint", key.fingerprint())
+            .property("status", key.trust())
+            .property("status-css-classes", status_css_classes.as_slice())
+            .property("expires", expires)
+            .property("master-sigs", master_sigs)
+            .property("packages", packages.join(", "))
+            .property("n-packages", packages.len() as u32)
+            .build()
+    }
+}
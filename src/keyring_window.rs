@@ -0,0 +1,367 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write as _;
+
+use gtk::{glib, gio, gdk};
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use glib::{clone, Propagation};
+use gdk::{Key, ModifierType};
+
+use crate::{
+    keyring::KeyringKey,
+    keyring_object::KeyringObject,
+    pkg_object::PkgObject
+};
+
+//------------------------------------------------------------------------------
+// MODULE: KeyringWindow
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate, glib::Properties)]
+    #[properties(wrapper_type = super::KeyringWindow)]
+    #[template(resource = "/com/github/PacView/ui/keyring_window.ui")]
+    pub struct KeyringWindow {
+        #[template_child]
+        pub(super) search_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub(super) search_bar: TemplateChild<gtk::SearchBar>,
+        #[template_child]
+        pub(super) search_entry: TemplateChild<gtk::SearchEntry>,
+
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) view: TemplateChild<gtk::ListView>,
+        #[template_child]
+        pub(super) model: TemplateChild<gio::ListStore>,
+        #[template_child]
+        pub(super) selection: TemplateChild<gtk::SingleSelection>,
+        #[template_child]
+        pub(super) search_filter: TemplateChild<gtk::CustomFilter>,
+        #[template_child]
+        pub(super) packager_filter: TemplateChild<gtk::CustomFilter>,
+
+        #[template_child]
+        pub(super) empty_status: TemplateChild<adw::StatusPage>,
+
+        #[template_child]
+        pub(super) footer_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) packager_label: TemplateChild<gtk::Label>,
+
+        #[property(get, set)]
+        is_loaded: Cell<bool>,
+        #[property(get, set)]
+        packagers_only: Cell<bool>,
+
+        pub(super) search_term: RefCell<String>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for KeyringWindow {
+        const NAME: &'static str = "KeyringWindow";
+        type Type = super::KeyringWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            KeyringObject::ensure_type();
+
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+
+            // Add key bindings
+            Self::bind_shortcuts(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for KeyringWindow {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+            obj.setup_widgets();
+        }
+    }
+
+    impl WidgetImpl for KeyringWindow {}
+    impl WindowImpl for KeyringWindow {}
+    impl AdwWindowImpl for KeyringWindow {}
+
+    impl KeyringWindow {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Packagers only property action
+            klass.install_property_action("keyring.packagers-only", "packagers-only");
+
+            // Copy action
+            klass.install_action("keyring.copy", None, |window, _, _| {
+                let mut output = String::from("## Pacman Keyring\n|User ID|Fingerprint|Status|Expires|Master Signatures|Installed Packages|\n|---|---|---|---|---|---|\n");
+
+                for key in window.imp().selection.iter::<glib::Object>()
+                    .flatten()
+                    .filter_map(|item| item.downcast::<KeyringObject>().ok()) {
+                        writeln!(output, "|{uid}|{fingerprint}|{status}|{expires}|{master_sigs}|{packages}|",
+                            uid=key.uid(),
+                            fingerprint=key.fingerprint(),
+                            status=key.status(),
+                            expires=key.expires(),
+                            master_sigs=key.master_sigs(),
+                            packages=key.n_packages()
+                        )
+                        .unwrap();
+                    }
+
+                window.clipboard().set_text(&output);
+            });
+        }
+
+        //---------------------------------------
+        // Bind shortcuts
+        //---------------------------------------
+        fn bind_shortcuts(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Close window binding
+            klass.add_binding_action(Key::Escape, ModifierType::NO_MODIFIER_MASK, "window.close");
+
+            // Find key binding
+            klass.add_binding(Key::F, ModifierType::CONTROL_MASK, |window| {
+                window.imp().search_bar.set_search_mode(true);
+
+                Propagation::Stop
+            });
+
+            // Packagers only key binding
+            klass.add_binding_action(Key::I, ModifierType::CONTROL_MASK, "keyring.packagers-only");
+
+            // Copy key binding
+            klass.add_binding_action(Key::C, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "keyring.copy");
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: KeyringWindow
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct KeyringWindow(ObjectSubclass<imp::KeyringWindow>)
+        @extends adw::Window, gtk::Window, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Native, gtk::Root, gtk::ShortcutManager;
+}
+
+impl KeyringWindow {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        let imp = self.imp();
+
+        // Search entry search changed signal
+        imp.search_entry.connect_search_changed(clone!(
+            #[weak] imp,
+            move |entry| {
+                let term = entry.text().trim().to_lowercase();
+
+                imp.search_term.replace(term);
+
+                imp.search_filter.changed(gtk::FilterChange::Different);
+            }
+        ));
+
+        // Packagers only property notify signal
+        self.connect_packagers_only_notify(|window| {
+            window.imp().packager_filter.changed(gtk::FilterChange::Different);
+        });
+
+        // Selection items changed signal
+        imp.selection.connect_items_changed(clone!(
+            #[weak(rename_to = window)] self,
+            move |selection, _, _, _| {
+                let imp = window.imp();
+
+                let n_items = selection.n_items();
+
+                imp.stack.set_visible_child_name(
+                    if n_items == 0 { "empty" } else { "view" }
+                );
+
+                imp.footer_label.set_label(&format!("{n_items} key{}", if n_items == 1 { "" } else { "s" }));
+
+                window.action_set_enabled("keyring.copy", n_items > 0);
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Setup widgets
+    //---------------------------------------
+    fn setup_widgets(&self) {
+        let imp = self.imp();
+
+        // Set search bar key capture widget
+        imp.search_bar.set_key_capture_widget(Some(&imp.view.get()));
+
+        // Bind search button state to search bar visibility
+        imp.search_button.bind_property("active", &imp.search_bar.get(), "search-mode-enabled")
+            .bidirectional()
+            .sync_create()
+            .build();
+
+        // Set search filter function
+        imp.search_filter.set_filter_func(clone!(
+            #[weak(rename_to = window)] self,
+            #[upgrade_or] false,
+            move |item| {
+                let search_term = window.imp().search_term.borrow();
+
+                if search_term.is_empty() {
+                    return true;
+                }
+
+                let key = item
+                    .downcast_ref::<KeyringObject>()
+                    .expect("Failed to downcast to 'KeyringObject'");
+
+                [key.uid(), key.other_uids(), key.fingerprint(), key.packages()].iter()
+                    .any(|prop| prop.to_lowercase().contains(search_term.as_str()))
+            }
+        ));
+
+        // Set packager filter function
+        imp.packager_filter.set_filter_func(clone!(
+            #[weak(rename_to = window)] self,
+            #[upgrade_or] false,
+            move |item| {
+                if window.packagers_only() {
+                    item
+                        .downcast_ref::<KeyringObject>()
+                        .expect("Failed to downcast to 'KeyringObject'")
+                        .n_packages() > 0
+                } else {
+                    true
+                }
+            }
+        ));
+
+        // Set initial focus on view
+        imp.view.grab_focus();
+    }
+
+    //---------------------------------------
+    // Populate window
+    //---------------------------------------
+    fn populate(&self, pkg_model: &gio::ListStore) {
+        let imp = self.imp();
+
+        glib::spawn_future_local(clone!(
+            #[weak] imp,
+            #[weak] pkg_model,
+            async move {
+                let keys = match KeyringKey::list(&[]).await {
+                    Ok(keys) => keys,
+                    Err(error) => {
+                        imp.empty_status.set_description(Some(&format!("Failed to read pacman keyring: {error}")));
+                        imp.model.remove_all();
+
+                        return
+                    }
+                };
+
+                imp.empty_status.set_description(None);
+
+                // Map packager emails to installed packages
+                let mut packager_map: HashMap<String, Vec<String>> = HashMap::new();
+
+                for pkg in pkg_model.iter::<PkgObject>().flatten().filter(PkgObject::is_installed) {
+                    if let Some(email) = pkg.packager().and_then(KeyringKey::uid_email) {
+                        packager_map.entry(email.to_lowercase())
+                            .or_default()
+                            .push(pkg.name());
+                    }
+                }
+
+                // Get master key IDs
+                let master_ids: HashSet<&str> = keys.iter()
+                    .filter(|key| key.is_master())
+                    .map(KeyringKey::key_id)
+                    .collect();
+
+                let key_list: Vec<KeyringObject> = keys.iter()
+                    .map(|key| {
+                        let master_sigs = key.signers().iter()
+                            .filter(|signer| master_ids.contains(signer.as_str()))
+                            .count();
+
+                        let mut packages: Vec<String> = key.emails().iter()
+                            .filter_map(|email| packager_map.get(&email.to_lowercase()))
+                            .flatten()
+                            .cloned()
+                            .collect();
+
+                        packages.sort_unstable();
+                        packages.dedup();
+
+                        KeyringObject::new(key, master_sigs as u32, &packages)
+                    })
+                    .collect();
+
+                let n_packagers = key_list.iter()
+                    .filter(|key| key.n_packages() > 0)
+                    .count();
+
+                imp.packager_label.set_label(&format!("{n_packagers} {} installed packages", if n_packagers == 1 { "key signs" } else { "keys sign" }));
+
+                imp.model.splice(0, imp.model.n_items(), &key_list);
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Show window
+    //---------------------------------------
+    pub fn show(&self, pkg_model: &gio::ListStore) {
+        self.present();
+
+        glib::idle_add_local_once(clone!(
+            #[weak(rename_to = window)] self,
+            #[weak] pkg_model,
+            move || {
+                if !window.is_loaded() {
+                    window.populate(&pkg_model);
+
+                    window.set_is_loaded(true);
+                }
+            }
+        ));
+    }
+}
+
+impl Default for KeyringWindow {
+    //---------------------------------------
+    // Default constructor
+    //---------------------------------------
+    fn default() -> Self {
+        glib::Object::builder().build()
+    }
+}
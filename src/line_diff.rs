@@ -0,0 +1,156 @@
+//------------------------------------------------------------------------------
+// ENUM: DiffKind
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    Same,
+    Removed,
+    Added,
+    Changed,
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: DiffRow
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct DiffRow<'a> {
+    pub old: Option<&'a str>,
+    pub new: Option<&'a str>,
+    pub kind: DiffKind
+}
+
+impl<'a> DiffRow<'a> {
+    //---------------------------------------
+    // Side by side diff function
+    //---------------------------------------
+    pub fn side_by_side(old: &'a str, new: &'a str) -> Vec<Self> {
+        let old_lines: Vec<&str> = old.lines().collect();
+        let new_lines: Vec<&str> = new.lines().collect();
+
+        let (n, m) = (old_lines.len(), new_lines.len());
+
+        // Longest common subsequence table
+        let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+        for i in (0..n).rev() {
+            for j in (0..m).rev() {
+                lcs[i][j] = if old_lines[i] == new_lines[j] {
+                    lcs[i + 1][j + 1] + 1
+                } else {
+                    lcs[i + 1][j].max(lcs[i][j + 1])
+                };
+            }
+        }
+
+        // Walk table, pairing adjacent removals and additions as changes
+        let mut rows: Vec<Self> = vec![];
+        let mut removed: Vec<&str> = vec![];
+        let mut added: Vec<&str> = vec![];
+
+        let flush = |rows: &mut Vec<Self>, removed: &mut Vec<&'a str>, added: &mut Vec<&'a str>| {
+            for k in 0..removed.len().max(added.len()) {
+                let old = removed.get(k).copied();
+                let new = added.get(k).copied();
+
+                let kind = match (old, new) {
+                    (Some(_), Some(_)) => DiffKind::Changed,
+                    (Some(_), None) => DiffKind::Removed,
+                    _ => DiffKind::Added
+                };
+
+                rows.push(Self { old, new, kind });
+            }
+
+            removed.clear();
+            added.clear();
+        };
+
+        let (mut i, mut j) = (0, 0);
+
+        while i < n || j < m {
+            if i < n && j < m && old_lines[i] == new_lines[j] {
+                flush(&mut rows, &mut removed, &mut added);
+
+                rows.push(Self { old: Some(old_lines[i]), new: Some(new_lines[j]), kind: DiffKind::Same });
+
+                i += 1;
+                j += 1;
+            } else if j < m && (i == n || lcs[i][j + 1] >= lcs[i + 1][j]) {
+                added.push(new_lines[j]);
+
+                j += 1;
+            } else {
+                removed.push(old_lines[i]);
+
+                i += 1;
+            }
+        }
+
+        flush(&mut rows, &mut removed, &mut added);
+
+        rows
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(old: &'static str, new: &'static str) -> Vec<(Option<&'static str>, Option<&'static str>, DiffKind)> {
+        DiffRow::side_by_side(old, new).into_iter()
+            .map(|row| (row.old, row.new, row.kind))
+            .collect()
+    }
+
+    #[test]
+    fn identical_text() {
+        assert_eq!(rows("a\nb", "a\nb"), [
+            (Some("a"), Some("a"), DiffKind::Same),
+            (Some("b"), Some("b"), DiffKind::Same),
+        ]);
+    }
+
+    #[test]
+    fn changed_line_is_paired() {
+        assert_eq!(rows("a\nb\nc", "a\nB\nc"), [
+            (Some("a"), Some("a"), DiffKind::Same),
+            (Some("b"), Some("B"), DiffKind::Changed),
+            (Some("c"), Some("c"), DiffKind::Same),
+        ]);
+    }
+
+    #[test]
+    fn added_and_removed_lines() {
+        assert_eq!(rows("a\nc", "a\nb\nc"), [
+            (Some("a"), Some("a"), DiffKind::Same),
+            (None, Some("b"), DiffKind::Added),
+            (Some("c"), Some("c"), DiffKind::Same),
+        ]);
+
+        assert_eq!(rows("a\nb\nc", "a\nc"), [
+            (Some("a"), Some("a"), DiffKind::Same),
+            (Some("b"), None, DiffKind::Removed),
+            (Some("c"), Some("c"), DiffKind::Same),
+        ]);
+    }
+
+    #[test]
+    fn uneven_change_block() {
+        assert_eq!(rows("x\ny\nz", "1\n2"), [
+            (Some("x"), Some("1"), DiffKind::Changed),
+            (Some("y"), Some("2"), DiffKind::Changed),
+            (Some("z"), None, DiffKind::Removed),
+        ]);
+    }
+
+    #[test]
+    fn empty_inputs() {
+        assert!(rows("", "").is_empty());
+
+        assert_eq!(rows("", "a"), [(None, Some("a"), DiffKind::Added)]);
+        assert_eq!(rows("a", ""), [(Some("a"), None, DiffKind::Removed)]);
+    }
+}
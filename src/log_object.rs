@@ -1,9 +1,12 @@
 use std::cell::RefCell;
+use std::sync::LazyLock;
 
 use gtk::glib;
 use gtk::subclass::prelude::*;
 use gtk::prelude::ObjectExt;
 
+use regex::Regex;
+
 //------------------------------------------------------------------------------
 // STRUCT: LogLine
 //------------------------------------------------------------------------------
@@ -14,6 +17,54 @@ pub struct LogLine {
     pub message: String
 }
 
+//------------------------------------------------------------------------------
+// STRUCT: PkgEvent
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct PkgEvent {
+    pub date: String,
+    pub time: String,
+    pub action: String,
+    pub name: String,
+    pub version: String
+}
+
+impl PkgEvent {
+    //---------------------------------------
+    // Parse package events from log
+    //---------------------------------------
+    pub fn from_log(log: &str) -> Vec<Self> {
+        static EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"\[([^T]+)T([^+]+)\+.+?\] \[ALPM\] (installed|removed|upgraded|downgraded|reinstalled) (.+?) \((.+)\)")
+                .expect("Failed to compile Regex")
+        });
+
+        log.lines()
+            .filter_map(|line| {
+                EXPR.captures(line)
+                    .map(|caps| Self {
+                        date: caps[1].to_string(),
+                        time: caps[2].to_string(),
+                        action: caps[3].to_string(),
+                        name: caps[4].to_string(),
+                        version: caps[5].to_string()
+                    })
+            })
+            .collect()
+    }
+
+    //---------------------------------------
+    // Version after event (None if removed)
+    //---------------------------------------
+    pub fn new_version(&self) -> Option<&str> {
+        if self.action == "removed" {
+            None
+        } else {
+            Some(self.version.rsplit_once(" -> ").map_or(self.version.as_str(), |(_, new)| new))
+        }
+    }
+}
+
 //------------------------------------------------------------------------------
 // MODULE: LogObject
 //------------------------------------------------------------------------------
@@ -70,3 +121,17 @@ impl LogObject {
             .build()
     }
 }
+
+impl From<&PkgEvent> for LogObject {
+    //---------------------------------------
+    // From package event function
+    //---------------------------------------
+    fn from(event: &PkgEvent) -> Self {
+        glib::Object::builder()
+            .property("date", &event.date)
+            .property("time", &event.time)
+            .property("category", &event.action)
+            .property("message", format!("{} ({})", event.name, event.version))
+            .build()
+    }
+}
@@ -10,28 +10,53 @@ mod info_pane;
 mod info_details_tab;
 mod info_files_tab;
 mod info_log_tab;
+mod info_aur_tab;
 mod hash_window;
 mod info_row;
 mod history_list;
 mod text_widget;
 mod preferences_dialog;
 mod stats_window;
+mod disk_usage_window;
+mod rollback_window;
+mod timeline_window;
+mod compare_window;
 mod backup_window;
 mod log_window;
 mod cache_window;
 mod groups_window;
+mod health_window;
+mod keyring_window;
+mod mirrors_window;
 mod config_dialog;
 mod config_row;
+mod provider_dialog;
 mod source_window;
+mod pkgbuild_review;
+mod line_diff;
+mod srcinfo;
+mod keyring;
+mod mirrors;
 mod pkg_data;
 mod pkg_object;
 mod stats_object;
+mod sync_db_object;
 mod backup_object;
 mod log_object;
 mod cache_object;
+mod rollback_object;
 mod groups_object;
+mod health_object;
+mod keyring_object;
+mod mirror_object;
+mod aur_helper;
+mod aur_db;
+mod aur_page;
 mod utils;
 
+#[cfg(test)]
+mod test_utils;
+
 use gtk::{gio, glib};
 use gtk::prelude::*;
 
@@ -0,0 +1,125 @@
+use std::cell::{Cell, RefCell, OnceCell};
+
+use gtk::glib;
+use gtk::subclass::prelude::*;
+use gtk::prelude::ObjectExt;
+
+use crate::mirrors::{Mirror, MirrorProbe};
+
+//------------------------------------------------------------------------------
+// MODULE: MirrorObject
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::MirrorObject)]
+    pub struct MirrorObject {
+        #[property(get, set, construct_only)]
+        repository: RefCell<String>,
+        #[property(get, set, construct_only)]
+        repo_index: Cell<u32>,
+        #[property(get, set, construct_only)]
+        url: RefCell<String>,
+
+        #[property(get, set)]
+        rank: Cell<u32>,
+        #[property(get, set)]
+        status: RefCell<String>,
+        #[property(get, set)]
+        status_css_classes: RefCell<Vec<String>>,
+        #[property(get, set)]
+        latency: RefCell<String>,
+        #[property(get, set)]
+        speed: RefCell<String>,
+        #[property(get, set)]
+        error: RefCell<String>,
+
+        pub(super) mirror: OnceCell<Mirror>,
+        pub(super) probe: RefCell<Option<MirrorProbe>>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for MirrorObject {
+        const NAME: &'static str = "MirrorObject";
+        type Type = super::MirrorObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for MirrorObject {}
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: MirrorObject
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct MirrorObject(ObjectSubclass<imp::MirrorObject>);
+}
+
+impl MirrorObject {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    pub fn new(mirror: &Mirror, repo_index: u32, rank: u32) -> Self {
+        let obj: Self = glib::Object::builder()
+            .property("repository", mirror.repository())
+            .property("repo-index", repo_index)
+            .property("url", mirror.url())
+            .property("rank", rank)
+            .property("latency", "\u{2014}")
+            .property("speed", "\u{2014}")
+            .build();
+
+        obj.imp().mirror.set(mirror.clone()).unwrap();
+
+        obj
+    }
+
+    //---------------------------------------
+    // Public mirror/probe functions
+    //---------------------------------------
+    pub fn mirror(&self) -> &Mirror {
+        self.imp().mirror.get().unwrap()
+    }
+
+    pub fn probe(&self) -> Option<MirrorProbe> {
+        self.imp().probe.borrow().clone()
+    }
+
+    pub fn set_probe(&self, probe: MirrorProbe) {
+        // Format last sync status
+        let (status, css_class) = if let Some(error) = probe.error().filter(|_| probe.speed().is_none()) {
+            self.set_error(error);
+
+            // Distinguish database download errors from unreachable servers
+            let status = if probe.is_reachable() { "Download failed" } else { "Unreachable" };
+
+            (String::from(status), "error")
+        } else if let Some(last_sync) = probe.last_sync() {
+            let age = glib::DateTime::now_utc().map_or(0, |now| now.to_unix() - last_sync).max(0);
+
+            let status = match age {
+                0..3600 => format!("Synced {} min ago", age / 60),
+                3600..86400 => format!("Synced {} h ago", age / 3600),
+                _ => format!("Synced {} d ago", age / 86400)
+            };
+
+            (status, if age < 86400 { "success" } else { "warning" })
+        } else {
+            (String::from("Unknown"), "dimmed")
+        };
+
+        self.set_status(status);
+        self.set_status_css_classes(vec![String::from("tag"), css_class.to_owned()]);
+        self.set_latency(probe.latency_string());
+        self.set_speed(probe.speed_string());
+
+        self.imp().probe.replace(Some(probe));
+    }
+}
@@ -0,0 +1,384 @@
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+use std::io;
+use std::time::{Duration, Instant};
+
+use futures_util::StreamExt;
+use size::Size;
+use tokio_util::sync::CancellationToken;
+
+use crate::utils::TokioUtils;
+
+//------------------------------------------------------------------------------
+// CONST variables
+//------------------------------------------------------------------------------
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+const SPEED_SAMPLE_SIZE: usize = 2 * 1024 * 1024;
+
+//------------------------------------------------------------------------------
+// STRUCT: MirrorProbe
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Clone)]
+pub struct MirrorProbe {
+    latency: Option<Duration>,
+    last_sync: Option<i64>,
+    speed: Option<f64>,
+    error: Option<String>
+}
+
+impl MirrorProbe {
+    pub fn last_sync(&self) -> Option<i64> {
+        self.last_sync
+    }
+
+    pub fn speed(&self) -> Option<f64> {
+        self.speed
+    }
+
+    pub fn error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+
+    pub fn is_reachable(&self) -> bool {
+        self.latency.is_some()
+    }
+
+    pub fn latency_string(&self) -> String {
+        self.latency.map_or_else(|| String::from("\u{2014}"), |latency| format!("{} ms", latency.as_millis()))
+    }
+
+    pub fn speed_string(&self) -> String {
+        self.speed.map_or_else(|| String::from("\u{2014}"), |speed| format!("{}/s", Size::from_bytes(speed as u64)))
+    }
+
+    //---------------------------------------
+    // Rank comparison function
+    //---------------------------------------
+    pub fn rank_cmp(&self, other: &Self) -> Ordering {
+        // Rank by download speed, then by latency, failed mirrors last
+        match (self.speed, other.speed) {
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => match (self.latency, other.latency) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal
+            }
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: Mirror
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct Mirror {
+    repository: String,
+    url: String
+}
+
+impl Mirror {
+    //---------------------------------------
+    // From config function
+    //---------------------------------------
+    pub fn from_config(config: &pacmanconf::Config) -> Vec<Self> {
+        config.repos.iter()
+            .flat_map(|repo| {
+                repo.servers.iter()
+                    .map(|server| Self {
+                        repository: repo.name.clone(),
+                        url: server.trim_end_matches('/').to_owned()
+                    })
+            })
+            .collect()
+    }
+
+    //---------------------------------------
+    // Public getters
+    //---------------------------------------
+    pub fn repository(&self) -> &str {
+        &self.repository
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    //---------------------------------------
+    // Root URL function
+    //---------------------------------------
+    pub fn root(&self) -> String {
+        let segment = format!("/{}/", self.repository);
+
+        if let Some(index) = self.url.find(&segment) {
+            self.url[..=index].to_owned()
+        } else if let Some(root) = self.url.strip_suffix(&self.repository) {
+            root.to_owned()
+        } else {
+            format!("{}/", self.url)
+        }
+    }
+
+    //---------------------------------------
+    // Server template function
+    //---------------------------------------
+    pub fn template(&self, arch: &str) -> String {
+        let segment = format!("/{}/", self.repository);
+
+        let Some(index) = self.url.find(&segment) else {
+            return self.url.clone()
+        };
+
+        let rest = &self.url[index + segment.len()..];
+
+        let rest = rest.strip_suffix(arch)
+            .map_or_else(|| rest.to_owned(), |rest| format!("{rest}$arch"));
+
+        format!("{}/$repo/{rest}", &self.url[..index])
+    }
+
+    //---------------------------------------
+    // Probe async function
+    //---------------------------------------
+    pub async fn probe(&self, token: CancellationToken) -> io::Result<MirrorProbe> {
+        let lastsync_url = self.root() + "lastsync";
+        let db_url = format!("{}/{}.db", self.url, self.repository);
+
+        TokioUtils::runtime().spawn(
+            async move {
+                let request = async {
+                    let client = reqwest::Client::builder()
+                        .timeout(PROBE_TIMEOUT)
+                        .build()
+                        .map_err(io::Error::other)?;
+
+                    let mut probe = MirrorProbe::default();
+
+                    // Measure latency and read last sync time
+                    let start = Instant::now();
+
+                    match client.get(&lastsync_url).send().await {
+                        Ok(response) => {
+                            probe.latency = Some(start.elapsed());
+
+                            if response.status().is_success() {
+                                probe.last_sync = response.text().await.ok()
+                                    .and_then(|text| text.trim().parse::<i64>().ok());
+                            }
+                        },
+                        Err(error) => {
+                            probe.error = Some(error.to_string());
+
+                            return Ok(probe)
+                        }
+                    }
+
+                    // Measure download speed
+                    match client.get(&db_url).send().await.and_then(reqwest::Response::error_for_status) {
+                        Ok(response) => {
+                            let start = Instant::now();
+                            let mut stream = response.bytes_stream();
+                            let mut received = 0;
+
+                            while let Some(chunk) = stream.next().await {
+                                match chunk {
+                                    Ok(chunk) => received += chunk.len(),
+                                    Err(error) => {
+                                        probe.error = Some(error.to_string());
+                                        break;
+                                    }
+                                }
+
+                                if received >= SPEED_SAMPLE_SIZE {
+                                    break;
+                                }
+                            }
+
+                            let elapsed = start.elapsed().as_secs_f64();
+
+                            if received > 0 && elapsed > 0.0 {
+                                probe.speed = Some(received as f64 / elapsed);
+                            }
+                        },
+                        Err(error) => {
+                            probe.error = Some(error.to_string());
+                        }
+                    }
+
+                    Ok(probe)
+                };
+
+                tokio::select! {
+                    result = request => result,
+                    () = token.cancelled() => {
+                        Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled by user"))
+                    }
+                }
+            }
+        )
+        .await
+        .expect("Failed to complete tokio task")
+    }
+
+    //---------------------------------------
+    // Ranked mirrorlist function
+    //---------------------------------------
+    pub fn ranked_mirrorlist(results: &[(Self, MirrorProbe)], arch: &str, header: &str) -> String {
+        // Merge probes for servers sharing the same template
+        let mut ranked: Vec<(String, &MirrorProbe)> = vec![];
+
+        let mut sorted: Vec<&(Self, MirrorProbe)> = results.iter().collect();
+        sorted.sort_by(|(_, a), (_, b)| a.rank_cmp(b));
+
+        for (mirror, probe) in sorted {
+            let template = mirror.template(arch);
+
+            if !ranked.iter().any(|(t, _)| *t == template) {
+                ranked.push((template, probe));
+            }
+        }
+
+        let mut output = String::from("##\n");
+
+        for line in header.lines() {
+            writeln!(output, "## {line}").unwrap();
+        }
+
+        output.push_str("##\n");
+
+        for (template, probe) in ranked {
+            output.push('\n');
+
+            if let Some(error) = probe.error().filter(|_| probe.speed().is_none()) {
+                let status = if probe.is_reachable() { "Download failed" } else { "Unreachable" };
+
+                writeln!(output, "## {status}: {error}\n#Server = {template}").unwrap();
+            } else {
+                writeln!(output, "## Latency: {}, Speed: {}\nServer = {template}",
+                    probe.latency_string(),
+                    probe.speed_string()
+                )
+                .unwrap();
+            }
+        }
+
+        output
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::net::TcpListener;
+
+    use crate::test_utils::{TestServer, TestResponse};
+
+    fn mirror(repository: &str, url: &str) -> Mirror {
+        Mirror { repository: repository.to_owned(), url: url.to_owned() }
+    }
+
+    fn probe(latency: Option<u64>, speed: Option<f64>, error: Option<&str>) -> MirrorProbe {
+        MirrorProbe {
+            latency: latency.map(Duration::from_millis),
+            last_sync: None,
+            speed,
+            error: error.map(ToOwned::to_owned)
+        }
+    }
+
+    #[test]
+    fn template_replaces_repo_and_arch() {
+        let core = mirror("core", "https://mirror.example.com/archlinux/core/os/x86_64");
+
+        assert_eq!(core.template("x86_64"), "https://mirror.example.com/archlinux/$repo/os/$arch");
+        assert_eq!(core.template("aarch64"), "https://mirror.example.com/archlinux/$repo/os/x86_64");
+        assert_eq!(core.root(), "https://mirror.example.com/archlinux/");
+
+        // Repository name at end of URL
+        let custom = mirror("custom", "https://example.com/repo/custom");
+
+        assert_eq!(custom.template("x86_64"), "https://example.com/repo/custom");
+        assert_eq!(custom.root(), "https://example.com/repo/");
+
+        // Local repository without repository name in URL
+        let local = mirror("aur", "file:///srv/packages");
+
+        assert_eq!(local.template("x86_64"), "file:///srv/packages");
+        assert_eq!(local.root(), "file:///srv/packages/");
+    }
+
+    #[test]
+    fn ranked_mirrorlist_merges_and_orders_servers() {
+        let results = [
+            (mirror("core", "https://slow.example.com/core/os/x86_64"), probe(Some(20), Some(1000.0), None)),
+            (mirror("extra", "https://slow.example.com/extra/os/x86_64"), probe(Some(20), Some(1000.0), None)),
+            (mirror("core", "https://fast.example.com/core/os/x86_64"), probe(Some(50), Some(5000.0), None)),
+            (mirror("core", "https://down.example.com/core/os/x86_64"), probe(None, None, Some("timed out"))),
+            (mirror("core", "https://broken.example.com/core/os/x86_64"), probe(Some(10), None, Some("404 Not Found"))),
+        ];
+
+        let output = Mirror::ranked_mirrorlist(&results, "x86_64", "Header line 1\nHeader line 2");
+
+        let servers: Vec<&str> = output.lines()
+            .filter(|line| line.contains("Server = "))
+            .collect();
+
+        assert_eq!(servers, [
+            "Server = https://fast.example.com/$repo/os/$arch",
+            "Server = https://slow.example.com/$repo/os/$arch",
+            "#Server = https://broken.example.com/$repo/os/$arch",
+            "#Server = https://down.example.com/$repo/os/$arch",
+        ]);
+
+        assert!(output.starts_with("##\n## Header line 1\n## Header line 2\n##\n"));
+        assert!(output.contains("## Download failed: 404 Not Found\n"));
+        assert!(output.contains("## Unreachable: timed out\n"));
+    }
+
+    #[test]
+    fn probe_local_server() {
+        let server = TestServer::start(|request| {
+            match request.path.as_str() {
+                "/archlinux/lastsync" => TestResponse::ok("1700000000\n"),
+                "/archlinux/core/os/x86_64/core.db" => TestResponse::ok(vec![0u8; 4096]),
+                _ => TestResponse::not_found()
+            }
+        });
+
+        let runtime = TokioUtils::runtime();
+
+        let ok = mirror("core", &format!("{}/archlinux/core/os/x86_64", server.url()));
+        let result = runtime.block_on(ok.probe(CancellationToken::new())).unwrap();
+
+        assert!(result.is_reachable());
+        assert_eq!(result.last_sync(), Some(1_700_000_000));
+        assert!(result.speed().is_some());
+        assert!(result.error().is_none());
+
+        // Server answers but database is missing
+        let missing = mirror("extra", &format!("{}/archlinux/extra/os/x86_64", server.url()));
+        let result = runtime.block_on(missing.probe(CancellationToken::new())).unwrap();
+
+        assert!(result.is_reachable());
+        assert!(result.speed().is_none());
+        assert!(result.error().is_some());
+    }
+
+    #[test]
+    fn probe_unreachable_server() {
+        // Bind and drop listener to get a closed local port
+        let addr = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+
+        let down = mirror("core", &format!("http://{addr}/core/os/x86_64"));
+        let result = TokioUtils::runtime().block_on(down.probe(CancellationToken::new())).unwrap();
+
+        assert!(!result.is_reachable());
+        assert!(result.error().is_some());
+    }
+}
@@ -0,0 +1,426 @@
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+use std::io;
+
+use gtk::{glib, gio, gdk};
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use glib::clone;
+use gdk::{Key, ModifierType};
+
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    mirrors::{Mirror, MirrorProbe},
+    mirror_object::MirrorObject,
+    utils::Pacman
+};
+
+//------------------------------------------------------------------------------
+// MODULE: MirrorsWindow
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate, glib::Properties)]
+    #[properties(wrapper_type = super::MirrorsWindow)]
+    #[template(resource = "/com/github/PacView/ui/mirrors_window.ui")]
+    pub struct MirrorsWindow {
+        #[template_child]
+        pub(super) test_button: TemplateChild<gtk::Button>,
+
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) view: TemplateChild<gtk::ListView>,
+        #[template_child]
+        pub(super) model: TemplateChild<gio::ListStore>,
+        #[template_child]
+        pub(super) section_sort_model: TemplateChild<gtk::SortListModel>,
+        #[template_child]
+        pub(super) selection: TemplateChild<gtk::SingleSelection>,
+        #[template_child]
+        pub(super) sorter: TemplateChild<gtk::NumericSorter>,
+
+        #[template_child]
+        pub(super) footer_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) progress_label: TemplateChild<gtk::Label>,
+
+        #[property(get, set)]
+        is_loaded: Cell<bool>,
+        #[property(get, set)]
+        is_testing: Cell<bool>,
+
+        pub(super) test_token: RefCell<Option<CancellationToken>>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for MirrorsWindow {
+        const NAME: &'static str = "MirrorsWindow";
+        type Type = super::MirrorsWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            MirrorObject::ensure_type();
+
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+
+            // Add key bindings
+            Self::bind_shortcuts(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for MirrorsWindow {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+            obj.setup_widgets();
+        }
+    }
+
+    impl WidgetImpl for MirrorsWindow {}
+    impl WindowImpl for MirrorsWindow {
+        //---------------------------------------
+        // Window close handler
+        //---------------------------------------
+        fn close_request(&self) -> glib::Propagation {
+            if let Some(token) = self.test_token.take() {
+                token.cancel();
+            }
+
+            self.parent_close_request()
+        }
+    }
+    impl AdwWindowImpl for MirrorsWindow {}
+
+    impl MirrorsWindow {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Test action
+            klass.install_action_async("mirrors.test", None, async |window, _, _| {
+                if window.is_testing() {
+                    if let Some(token) = window.imp().test_token.take() {
+                        token.cancel();
+                    }
+                } else {
+                    window.test_mirrors().await;
+                }
+            });
+
+            // Save action
+            klass.install_action_async("mirrors.save", None, async |window, _, _| {
+                let file_dialog = gtk::FileDialog::builder()
+                    .modal(true)
+                    .title("Save Ranked Mirrorlist")
+                    .initial_name("mirrorlist")
+                    .build();
+
+                if let Ok(file) = file_dialog.save_future(Some(&window)).await {
+                    let _ = file.replace_contents_future(
+                        window.ranked_mirrorlist().into_bytes(),
+                        None,
+                        false,
+                        gio::FileCreateFlags::REPLACE_DESTINATION
+                    )
+                    .await;
+                }
+            });
+
+            // Copy action
+            klass.install_action("mirrors.copy", None, |window, _, _| {
+                let mut repository = String::new();
+                let mut output = String::from("## Pacman Mirrors\n|Server|Latency|Speed|Status|\n|---|---|---|---|\n");
+
+                for mirror in window.imp().selection.iter::<glib::Object>()
+                    .flatten()
+                    .filter_map(|item| item.downcast::<MirrorObject>().ok()) {
+                        let mirror_repo = mirror.repository();
+
+                        if mirror_repo != repository {
+                            writeln!(output, "|**{mirror_repo}**||||").unwrap();
+
+                            repository = mirror_repo;
+                        }
+
+                        writeln!(output, "|{url}|{latency}|{speed}|{status}|",
+                            url=mirror.url(),
+                            latency=mirror.latency(),
+                            speed=mirror.speed(),
+                            status=mirror.status()
+                        )
+                        .unwrap();
+                    }
+
+                window.clipboard().set_text(&output);
+            });
+        }
+
+        //---------------------------------------
+        // Bind shortcuts
+        //---------------------------------------
+        fn bind_shortcuts(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Close window binding
+            klass.add_binding_action(Key::Escape, ModifierType::NO_MODIFIER_MASK, "window.close");
+
+            // Test key binding
+            klass.add_binding_action(Key::T, ModifierType::CONTROL_MASK, "mirrors.test");
+
+            // Save key binding
+            klass.add_binding_action(Key::S, ModifierType::CONTROL_MASK, "mirrors.save");
+
+            // Copy key binding
+            klass.add_binding_action(Key::C, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "mirrors.copy");
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: MirrorsWindow
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct MirrorsWindow(ObjectSubclass<imp::MirrorsWindow>)
+        @extends adw::Window, gtk::Window, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Native, gtk::Root, gtk::ShortcutManager;
+}
+
+impl MirrorsWindow {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        let imp = self.imp();
+
+        // Is testing property notify signal
+        self.connect_is_testing_notify(|window| {
+            let imp = window.imp();
+
+            if window.is_testing() {
+                imp.test_button.set_icon_name("media-playback-stop-symbolic");
+                imp.test_button.set_tooltip_text(Some("Stop Testing Mirrors"));
+            } else {
+                imp.test_button.set_icon_name("media-playback-start-symbolic");
+                imp.test_button.set_tooltip_text(Some("Test Mirrors"));
+            }
+        });
+
+        // Section sort model items changed signal
+        imp.section_sort_model.connect_items_changed(clone!(
+            #[weak(rename_to = window)] self,
+            move |sort_model, _, _, _| {
+                let imp = window.imp();
+
+                let n_items = sort_model.n_items();
+                let mut n_sections = 0;
+                let mut index = 0;
+
+                while index < n_items {
+                    let (_, end) = sort_model.section(index);
+
+                    n_sections += 1;
+                    index = end;
+                }
+
+                imp.stack.set_visible_child_name(
+                    if n_items == 0 { "empty" } else { "view" }
+                );
+
+                imp.footer_label.set_label(&format!("{n_items} server{} in {n_sections} repositor{}",
+                    if n_items == 1 { "" } else { "s" },
+                    if n_sections == 1 { "y" } else { "ies" }
+                ));
+
+                window.action_set_enabled("mirrors.test", n_items > 0);
+                window.action_set_enabled("mirrors.copy", n_items > 0);
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Setup widgets
+    //---------------------------------------
+    fn setup_widgets(&self) {
+        self.action_set_enabled("mirrors.save", false);
+
+        // Set initial focus on view
+        self.imp().view.grab_focus();
+    }
+
+    //---------------------------------------
+    // Test mirrors async function
+    //---------------------------------------
+    async fn test_mirrors(&self) {
+        let imp = self.imp();
+
+        let token = CancellationToken::new();
+        imp.test_token.replace(Some(token.clone()));
+
+        self.set_is_testing(true);
+
+        self.action_set_enabled("mirrors.save", false);
+
+        let mirrors: Vec<MirrorObject> = imp.model.iter::<MirrorObject>()
+            .flatten()
+            .collect();
+
+        // Group mirrors by host, so servers shared by several repositories are only probed once
+        let mut hosts: Vec<(String, Vec<&MirrorObject>)> = vec![];
+
+        for mirror in &mirrors {
+            let root = mirror.mirror().root();
+
+            if let Some((_, group)) = hosts.iter_mut().find(|(host, _)| *host == root) {
+                group.push(mirror);
+            } else {
+                hosts.push((root, vec![mirror]));
+            }
+        }
+
+        let n_hosts = hosts.len();
+        let mut cancelled = false;
+
+        for (i, (_, group)) in hosts.iter().enumerate() {
+            imp.progress_label.set_label(&format!("Testing server {} of {n_hosts}\u{2026}", i + 1));
+
+            match group[0].mirror().probe(token.clone()).await {
+                Ok(probe) => {
+                    for mirror in group {
+                        mirror.set_probe(probe.clone());
+                    }
+                },
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {
+                    cancelled = true;
+                    break;
+                },
+                Err(_) => {}
+            }
+        }
+
+        // Rank mirrors by probe results, untested mirrors last
+        let mut ranked: Vec<(&MirrorObject, Option<MirrorProbe>)> = mirrors.iter()
+            .map(|mirror| (mirror, mirror.probe()))
+            .collect();
+
+        ranked.sort_by(|(_, a), (_, b)| {
+            match (a, b) {
+                (Some(a), Some(b)) => a.rank_cmp(b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal
+            }
+        });
+
+        for (rank, (mirror, _)) in ranked.into_iter().enumerate() {
+            mirror.set_rank(rank as u32);
+        }
+
+        self.action_set_enabled("mirrors.save", mirrors.iter().any(|mirror| mirror.probe().is_some()));
+
+        imp.sorter.changed(gtk::SorterChange::Different);
+
+        imp.progress_label.set_label(if cancelled { "Testing cancelled" } else { "Testing complete" });
+
+        imp.test_token.replace(None);
+
+        self.set_is_testing(false);
+    }
+
+    //---------------------------------------
+    // Ranked mirrorlist function
+    //---------------------------------------
+    fn ranked_mirrorlist(&self) -> String {
+        let results: Vec<(Mirror, _)> = self.imp().model.iter::<MirrorObject>()
+            .flatten()
+            .filter_map(|mirror| mirror.probe().map(|probe| (mirror.mirror().clone(), probe)))
+            .collect();
+
+        let arch = Pacman::config().architecture.first()
+            .map_or("x86_64", String::as_str);
+
+        let date = glib::DateTime::now_local()
+            .and_then(|datetime| datetime.format("%c"))
+            .unwrap_or_default();
+
+        Mirror::ranked_mirrorlist(
+            &results,
+            arch,
+            &format!("Pacman mirrorlist generated by PacView\nRanked by download speed on {date}")
+        )
+    }
+
+    //---------------------------------------
+    // Populate window
+    //---------------------------------------
+    fn populate(&self) {
+        let imp = self.imp();
+
+        let config = Pacman::config();
+
+        let mirrors: Vec<MirrorObject> = Mirror::from_config(config).iter()
+            .enumerate()
+            .map(|(i, mirror)| {
+                let repo_index = config.repos.iter()
+                    .position(|repo| repo.name == mirror.repository())
+                    .unwrap_or_default();
+
+                MirrorObject::new(mirror, repo_index as u32, i as u32)
+            })
+            .collect();
+
+        imp.model.splice(0, imp.model.n_items(), &mirrors);
+
+        imp.progress_label.set_label("");
+
+        self.action_set_enabled("mirrors.save", false);
+    }
+
+    //---------------------------------------
+    // Show window
+    //---------------------------------------
+    pub fn show(&self) {
+        self.present();
+
+        glib::idle_add_local_once(clone!(
+            #[weak(rename_to = window)] self,
+            move || {
+                if !window.is_loaded() && !window.is_testing() {
+                    window.populate();
+
+                    window.set_is_loaded(true);
+                }
+            }
+        ));
+    }
+}
+
+impl Default for MirrorsWindow {
+    //---------------------------------------
+    // Default constructor
+    //---------------------------------------
+    fn default() -> Self {
+        glib::Object::builder().build()
+    }
+}
@@ -13,14 +13,18 @@ use tokio::sync::Mutex as TokioMutex;
 use tokio_util::sync::CancellationToken;
 use raur::Raur;
 use futures::future;
+use size::Size;
 
 use crate::{
+    APP_ID,
     package_item::PackageItem,
-    pkg_data::{PkgFlags, PkgData},
+    pkg_data::{PkgFlags, PkgData, AurMetadata},
     pkg_object::PkgObject,
     search_bar::{SearchBar, SearchProp},
     info_pane::InfoPane,
     utils::TokioUtils,
+    aur_page::AurPage,
+    aur_db::AurDb,
 };
 
 //------------------------------------------------------------------------------
@@ -89,6 +93,10 @@ mod imp {
         pub(super) stack: TemplateChild<gtk::Stack>,
         #[template_child]
         pub(super) loading_status: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub(super) download_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        pub(super) download_progress: TemplateChild<gtk::ProgressBar>,
 
         #[property(get)]
         #[template_child]
@@ -486,14 +494,20 @@ impl PackageView {
     }
 
     //---------------------------------------
-    // Do search helper function
+    // AUR cache helper function
     //---------------------------------------
-    async fn do_search(term: &str, tokens: &[String], prop: SearchProp) -> Result<Vec<PkgData>, raur::Error> {
-        // Static AUR cache variable
+    fn aur_cache() -> &'static TokioMutex<raur::Cache> {
         static AUR_CACHE: LazyLock<TokioMutex<raur::Cache>> = LazyLock::new(|| {
             TokioMutex::new(raur::Cache::default())
         });
 
+        &AUR_CACHE
+    }
+
+    //---------------------------------------
+    // Do search helper function
+    //---------------------------------------
+    async fn do_search(term: &str, tokens: &[String], prop: SearchProp, use_local: bool) -> Result<Vec<PkgData>, raur::Error> {
         // Return if query arg too small
         if term.len() < 2 {
             return Err(raur::Error::Aur(String::from("Query arg too small.")))
@@ -515,8 +529,40 @@ impl PackageView {
             SearchProp::Files => unreachable!(),
         };
 
+        // Search local AUR database if available
+        if use_local {
+            let tokens = tokens.to_vec();
+
+            let local_result = tokio::task::spawn_blocking(move || {
+                let aur_db = AurDb::shared()?;
+
+                let mut search_names: HashSet<String> = HashSet::new();
+                let mut pkg_data: Vec<PkgData> = vec![];
+
+                for token in &tokens {
+                    let pkgs = match aur_db.search(token, search_by) {
+                        Ok(pkgs) => pkgs,
+                        Err(error) => return Some(Err(raur::Error::Aur(error.to_string())))
+                    };
+
+                    pkg_data.extend(pkgs.iter()
+                        .filter(|pkg| search_names.insert(pkg.name.clone()))
+                        .map(PkgData::from_aur)
+                    );
+                }
+
+                Some(Ok(pkg_data))
+            })
+            .await
+            .expect("Failed to complete tokio task");
+
+            if let Some(result) = local_result {
+                return result
+            }
+        }
+
         // Search for AUR packages
-        let handle = raur::Handle::new();
+        let handle = raur::Handle::new_with_url(AurPage::rpc_url());
 
         let search_results = future::join_all(tokens.iter()
             .map(|t| handle.search_by(t, search_by))
@@ -531,7 +577,7 @@ impl PackageView {
 
         // Get AUR package info using cache
         let pkg_data = handle.cache_info(
-            &mut *AUR_CACHE.lock().await,
+            &mut *Self::aur_cache().lock().await,
             &search_names.iter().map(String::as_str).collect::<Vec<&str>>()
         )
         .await?
@@ -572,6 +618,8 @@ impl PackageView {
         let tokens = imp.search_tokens.borrow().to_owned();
         let prop = search_bar.prop();
 
+        let use_local = gio::Settings::new(APP_ID).boolean("aur-database-download");
+
         // Reset AUR search
         self.reset_aur_search();
 
@@ -600,7 +648,7 @@ impl PackageView {
                     async move {
                         tokio::select! {
                             () = cancel_token_clone.cancelled() => Ok(vec![]),
-                            pkg_data = Self::do_search(&term, &tokens, prop) => pkg_data
+                            pkg_data = Self::do_search(&term, &tokens, prop, use_local) => pkg_data
                         }
                     }
                 )
@@ -640,6 +688,8 @@ impl PackageView {
     pub fn set_state(&self, state: PackageViewState) {
         let imp = self.imp();
 
+        imp.download_box.set_visible(state == PackageViewState::AURDownload);
+
         match state {
             PackageViewState::Normal => {
                 imp.stack.set_visible_child_name("view");
@@ -651,10 +701,26 @@ impl PackageView {
             PackageViewState::AURDownload => {
                 imp.loading_status.set_title("Updating AUR Database");
                 imp.stack.set_visible_child_name("spinner");
+
+                imp.download_progress.set_fraction(0.0);
+                imp.download_progress.set_text(Some("Connecting"));
             }
         }
     }
 
+    pub fn set_download_progress(&self, received: u64, total: Option<u64>) {
+        let progress = &self.imp().download_progress;
+
+        // Pulse progress bar if download size is unknown
+        if let Some(total) = total.filter(|&total| total > 0) {
+            progress.set_fraction(received as f64 / total as f64);
+            progress.set_text(Some(&format!("{} of {}", Size::from_bytes(received), Size::from_bytes(total))));
+        } else {
+            progress.pulse();
+            progress.set_text(Some(&Size::from_bytes(received).to_string()));
+        }
+    }
+
     //---------------------------------------
     // Public package functions
     //---------------------------------------
@@ -667,14 +733,76 @@ impl PackageView {
         imp.pkg_model.splice(position, removals, pkg_slice);
     }
 
-    pub fn show_updates(&self, update_map: &HashMap<&str, &str>) {
+    pub fn show_updates(&self, update_map: &HashMap<&str, &str>, aur_checked: bool) {
         for pkg in self.imp().pkg_model.iter::<PkgObject>().flatten() {
             if let Some(&new_version) = update_map.get(&pkg.name().as_str()) {
                 pkg.set_update_version(Some(new_version));
             }
+
+            if aur_checked && pkg.repository() == "aur" {
+                pkg.set_update_checked(true);
+            }
         }
     }
 
+    //---------------------------------------
+    // Public show AUR metadata function
+    //---------------------------------------
+    pub async fn show_aur_metadata(&self) -> Result<(), raur::Error> {
+        // Clear AUR info cache on package reload so metadata is not stale
+        Self::aur_cache().lock().await.clear();
+
+        // Get installed AUR packages
+        let aur_pkgs: HashMap<String, PkgObject> = self.imp().pkg_model.iter::<PkgObject>()
+            .flatten()
+            .filter(|pkg| pkg.is_installed() && pkg.repository() == "aur")
+            .map(|pkg| (pkg.name(), pkg))
+            .collect();
+
+        if aur_pkgs.is_empty() {
+            return Ok(())
+        }
+
+        let names: Vec<String> = aur_pkgs.keys().cloned().collect();
+
+        let use_local = gio::Settings::new(APP_ID).boolean("aur-database-download");
+
+        // Spawn tokio task to batch query AUR info from local database or using cache
+        let aur_info: Vec<raur::Package> = TokioUtils::runtime().spawn(
+            async move {
+                if use_local {
+                    let local_names = names.clone();
+
+                    let local_info = tokio::task::spawn_blocking(move || {
+                        AurDb::shared().and_then(|aur_db| aur_db.info(&local_names).ok())
+                    })
+                    .await
+                    .expect("Failed to complete tokio task");
+
+                    if let Some(local_info) = local_info {
+                        return Ok(local_info)
+                    }
+                }
+
+                raur::Handle::new_with_url(AurPage::rpc_url())
+                    .cache_info(&mut *Self::aur_cache().lock().await, &names)
+                    .await
+                    .map(|aur_info| aur_info.iter().map(|pkg| (**pkg).clone()).collect())
+            }
+        )
+        .await
+        .expect("Failed to complete tokio task")?;
+
+        // Merge AUR metadata into installed packages
+        for aur_pkg in &aur_info {
+            if let Some(pkg) = aur_pkgs.get(&aur_pkg.name) {
+                pkg.set_aur_metadata(AurMetadata::from_aur(aur_pkg));
+            }
+        }
+
+        Ok(())
+    }
+
     //---------------------------------------
     // Public copy list function
     //---------------------------------------
@@ -18,6 +18,16 @@ pub enum PkgFlags {
     ORPHAN     = 0b0000_1000,
     NONE       = 0b0001_0000,
     UPDATES    = 0b0010_0000,
+    #[flags_value(name = "Newer Than Repo")]
+    NEWER      = 0b0100_0000,
+    #[flags_value(name = "Dropped From Repo")]
+    DROPPED    = 0b1000_0000,
+    #[flags_value(name = "Repo Changed")]
+    MOVED      = 0b0001_0000_0000,
+    #[flags_value(name = "Orphaned On AUR")]
+    ORPHANED   = 0b0010_0000_0000,
+    #[flags_value(name = "Flagged Out Of Date")]
+    OUTDATED   = 0b0100_0000_0000,
 }
 
 impl Default for PkgFlags {
@@ -69,6 +79,75 @@ impl Default for PkgValidation {
     }
 }
 
+//------------------------------------------------------------------------------
+// ENUM: PkgOrigin
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub enum PkgOrigin {
+    #[default]
+    Repository,
+    Repackaged(String),
+    Aur,
+    AurDeleted,
+    LocalBuild,
+    RemovedRepo(String),
+    Unknown,
+}
+
+impl Display for PkgOrigin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Repository => write!(f, "Repository"),
+            Self::Repackaged(packager) => write!(f, "Rebuilt locally (repository package by {packager})"),
+            Self::Aur => write!(f, "AUR"),
+            Self::AurDeleted => write!(f, "AUR (deleted from AUR)"),
+            Self::LocalBuild => write!(f, "Built from local PKGBUILD"),
+            Self::RemovedRepo(repo) => write!(f, "Repository \"{repo}\" (no longer configured)"),
+            Self::Unknown => write!(f, "Unknown foreign package")
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: AurMetadata
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Clone)]
+pub struct AurMetadata {
+    pub popularity: String,
+    pub out_of_date: Option<i64>,
+    pub maintainer: Option<String>,
+}
+
+impl AurMetadata {
+    //---------------------------------------
+    // AUR constructor
+    //---------------------------------------
+    pub fn from_aur(pkg: &raur::Package) -> Self {
+        Self {
+            popularity: format!("{:.2?} ({} vote{})", pkg.popularity, pkg.num_votes, if pkg.num_votes == 1 { "" } else { "s" }),
+            out_of_date: pkg.out_of_date,
+            maintainer: pkg.maintainer.clone(),
+        }
+    }
+
+    //---------------------------------------
+    // Flags function
+    //---------------------------------------
+    pub fn flags(&self) -> PkgFlags {
+        let mut flags = PkgFlags::empty();
+
+        if self.maintainer.is_none() {
+            flags |= PkgFlags::ORPHANED;
+        }
+
+        if self.out_of_date.is_some() {
+            flags |= PkgFlags::OUTDATED;
+        }
+
+        flags
+    }
+}
+
 //------------------------------------------------------------------------------
 // STRUCT: PkgData
 //------------------------------------------------------------------------------
@@ -89,6 +168,7 @@ pub struct PkgData {
     pub depends: Vec<String>,
     pub optdepends: Vec<String>,
     pub makedepends: Vec<String>,
+    pub checkdepends: Vec<String>,
     pub provides: Vec<String>,
     pub conflicts: Vec<String>,
     pub replaces: Vec<String>,
@@ -100,6 +180,7 @@ pub struct PkgData {
     pub install_size: i64,
     pub has_script: Option<String>,
     pub validation: PkgValidation,
+    pub origin: PkgOrigin,
 }
 
 //------------------------------------------------------------------------------
@@ -149,6 +230,7 @@ impl PkgData {
             depends: deplist_to_vec(pkg.depends()),
             optdepends: deplist_to_vec(pkg.optdepends()),
             makedepends: vec![],
+            checkdepends: deplist_to_vec(pkg.checkdepends()),
             provides: deplist_to_vec(pkg.provides()),
             conflicts: deplist_to_vec(pkg.conflicts()),
             replaces: deplist_to_vec(pkg.replaces()),
@@ -160,6 +242,7 @@ impl PkgData {
             install_size: pkg.isize(),
             has_script: pkg.has_scriptlet().then(|| "Yes".into()),
             validation: PkgValidation::from_bits_truncate(pkg.validation().bits()),
+            origin: PkgOrigin::Repository,
         }
     }
 
@@ -173,18 +256,18 @@ impl PkgData {
             slice.iter().map(String::from).sorted_unstable().collect()
         }
 
-        println!("{:?}", pkg.package_base);
-
         // Build PkgData
+        let metadata = AurMetadata::from_aur(pkg);
+
         Self {
-            flags: PkgFlags::NONE,
+            flags: PkgFlags::NONE | metadata.flags(),
             is_installed: false,
-            base: None,
+            base: Some(pkg.package_base.clone()),
             name: pkg.name.clone(),
             version: pkg.version.clone(),
             description: pkg.description.clone(),
-            popularity: Some(format!("{:.2?} ({} vote{})", pkg.popularity, pkg.num_votes, if pkg.num_votes == 1 { "" } else { "s" })),
-            out_of_date: pkg.out_of_date,
+            popularity: Some(metadata.popularity),
+            out_of_date: metadata.out_of_date,
             url: pkg.url.clone(),
             licenses: sorted_vec(&pkg.license),
             repository: "aur".into(),
@@ -192,6 +275,7 @@ impl PkgData {
             depends: sorted_vec(&pkg.depends),
             optdepends: sorted_vec(&pkg.opt_depends),
             makedepends: sorted_vec(&pkg.make_depends),
+            checkdepends: sorted_vec(&pkg.check_depends),
             provides: sorted_vec(&pkg.provides),
             conflicts: sorted_vec(&pkg.conflicts),
             replaces: sorted_vec(&pkg.replaces),
@@ -203,6 +287,7 @@ impl PkgData {
             install_size: 0,
             has_script: None,
             validation: PkgValidation::NONE,
+            origin: PkgOrigin::Aur,
         }
     }
 }
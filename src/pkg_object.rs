@@ -1,6 +1,12 @@
-use std::cell::{RefCell, OnceCell};
+use std::cell::{Cell, RefCell, OnceCell};
+use std::rc::Rc;
 use std::sync::LazyLock;
 use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::fs;
+use std::io;
 
 use gtk::{glib, gio};
 use gtk::subclass::prelude::*;
@@ -12,10 +18,14 @@ use alpm_utils::DbListExt;
 use regex::Regex;
 use size::Size;
 use tokio::sync::OnceCell as TokioOnceCell;
+use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
 
 use crate::{
-    utils::{Paths, Pacman},
-    pkg_data::{PkgData, PkgFlags, PkgValidation}
+    aur_page::AurPage,
+    utils::{Pacman, PkgbuildSource},
+    pkg_data::{PkgData, PkgFlags, PkgValidation, PkgOrigin, AurMetadata},
+    srcinfo::SrcInfo
 };
 
 //------------------------------------------------------------------------------
@@ -76,6 +86,41 @@ impl PkgHashes {
     }
 }
 
+//------------------------------------------------------------------------------
+// STRUCT: PkgDiskUsage
+//------------------------------------------------------------------------------
+#[derive(Default, Debug)]
+pub struct PkgDiskUsage {
+    owned_size: i64,
+    untracked_size: i64,
+    untracked_files: usize
+}
+
+impl PkgDiskUsage {
+    pub fn owned_size(&self) -> i64 {
+        self.owned_size
+    }
+
+    pub fn untracked_size(&self) -> i64 {
+        self.untracked_size
+    }
+
+    pub fn untracked_files(&self) -> usize {
+        self.untracked_files
+    }
+
+    pub fn total_size(&self) -> i64 {
+        self.owned_size + self.untracked_size
+    }
+
+    pub fn is_discrepant(&self, declared_size: i64) -> bool {
+        // Flag differences larger than 1 MiB and 25% of the declared size
+        let delta = (self.total_size() - declared_size).abs();
+
+        delta > 1024 * 1024 && delta * 4 > declared_size
+    }
+}
+
 //------------------------------------------------------------------------------
 // MODULE: PkgObject
 //------------------------------------------------------------------------------
@@ -91,6 +136,8 @@ mod imp {
         // Read-write properties
         #[property(get, set, nullable)]
         update_version: RefCell<Option<String>>,
+        #[property(get, set)]
+        update_checked: Cell<bool>,
 
         // Read-only properties with getter
         #[property(name = "flags", get = Self::flags, type = PkgFlags)]
@@ -102,13 +149,19 @@ mod imp {
         pub(super) data: OnceCell<PkgData>,
 
         // Read only fields
+        pub(super) aur_metadata: OnceCell<AurMetadata>,
+
         pub(super) required_by: OnceCell<Vec<String>>,
         pub(super) optional_for: OnceCell<Vec<String>>,
+        pub(super) base_siblings: OnceCell<Vec<String>>,
 
         pub(super) files: OnceCell<Vec<String>>,
         pub(super) backup: OnceCell<Vec<PkgBackup>>,
 
         pub(super) log: TokioOnceCell<Vec<String>>,
+        pub(super) disk_usage: TokioOnceCell<Option<PkgDiskUsage>>,
+        #[property(name = "has-srcinfo", get = Self::has_srcinfo, type = bool)]
+        pub(super) srcinfo: OnceCell<SrcInfo>,
     }
 
     //---------------------------------------
@@ -130,9 +183,16 @@ mod imp {
         fn flags(&self) -> PkgFlags {
             let flags = self.data.get().unwrap().flags;
 
+            let flags = self.aur_metadata.get()
+                .map_or(flags, |metadata| flags | metadata.flags());
+
             self.update_version.borrow().as_ref()
                 .map_or(flags, |_| flags | PkgFlags::UPDATES)
         }
+
+        fn has_srcinfo(&self) -> bool {
+            self.srcinfo.get().is_some()
+        }
     }
 }
 
@@ -159,6 +219,15 @@ impl PkgObject {
         pkg
     }
 
+    //---------------------------------------
+    // Set AUR metadata function
+    //---------------------------------------
+    pub fn set_aur_metadata(&self, metadata: AurMetadata) {
+        if self.imp().aur_metadata.set(metadata).is_ok() {
+            self.notify_flags();
+        }
+    }
+
     //---------------------------------------
     // Data field properties
     //---------------------------------------
@@ -177,10 +246,12 @@ impl PkgObject {
 
     pub fn popularity(&self) -> Option<&str> {
         self.data().popularity.as_deref()
+            .or_else(|| self.imp().aur_metadata.get().map(|metadata| metadata.popularity.as_str()))
     }
 
     pub fn out_of_date(&self) -> Option<i64> {
         self.data().out_of_date
+            .or_else(|| self.imp().aur_metadata.get().and_then(|metadata| metadata.out_of_date))
     }
 
     pub fn out_of_date_string(&self) -> Option<GString> {
@@ -197,7 +268,7 @@ impl PkgObject {
 
         match repo.as_str() {
             "aur" => {
-                Some(format!("https://aur.archlinux.org/packages/{}", data.name))
+                Some(format!("{}/packages/{}", AurPage::url(), data.name))
             }
             _ if Pacman::config().repos.iter().any(|r| &r.name == repo) => {
                 Some(format!("https://www.archlinux.org/packages/{}/{}/{}/",
@@ -210,41 +281,32 @@ impl PkgObject {
 
     }
 
-    pub fn pkgbuild_url(&self) -> Option<String> {
+    pub fn base(&self) -> &str {
         let data = self.data();
-        let name = data.base.as_ref().unwrap_or(&data.name);
-        let repo = &data.repository;
 
-        match repo.as_str() {
-            "aur" => {
-                let domain = "https://aur.archlinux.org/cgit/aur.git";
+        data.base.as_deref().unwrap_or(&data.name)
+    }
 
-                Some(format!("{domain}/tree/PKGBUILD?h={name}"))
-            }
-            _ if Pacman::config().repos.iter().any(|r| &r.name == repo) => {
-                let domain = "https://gitlab.archlinux.org/archlinux/packaging/packages";
+    pub fn pkgbuild_url(&self) -> Option<String> {
+        let name = self.base();
+        let repo = &self.data().repository;
 
-                Some(format!("{domain}/{name}/-/blob/main/PKGBUILD"))
+        match repo.as_str() {
+            "aur" => {
+                Some(format!("{}/tree/PKGBUILD?h={name}", PkgbuildSource::aur_url()))
             }
-            "local" => {
-                None
+            _ if PkgbuildSource::is_official_repo(repo) => {
+                Some(format!("{}/{}/-/blob/main/PKGBUILD", PkgbuildSource::repo_url(), PkgbuildSource::gitlab_project(name)))
             }
             _ => {
-                Paths::paru().as_ref().ok()
-                    .map(|_| {
-                        let raw_url = glib::user_cache_dir()
-                            .join(format!("paru/clone/repo/{repo}/{name}/PKGBUILD"))
-                            .display()
-                            .to_string();
-
-                        format!("file://{raw_url}")
-                    })
+                PkgbuildSource::clone_path(repo, name, "PKGBUILD")
+                    .map(|path| format!("file://{}", path.display()))
             }
         }
     }
 
     pub fn status(&self) -> &str {
-        match self.data().flags {
+        match self.data().flags.intersection(PkgFlags::INSTALLED) {
             PkgFlags::EXPLICIT => "explicit",
             PkgFlags::DEPENDENCY => "dependency",
             PkgFlags::OPTIONAL => "optional",
@@ -254,7 +316,7 @@ impl PkgObject {
     }
 
     pub fn status_css_classes(&self) -> Vec<&str> {
-        match self.data().flags {
+        match self.data().flags.intersection(PkgFlags::INSTALLED) {
             PkgFlags::ORPHAN => vec!["tag", "warning"],
             PkgFlags::EXPLICIT | PkgFlags::DEPENDENCY | PkgFlags::OPTIONAL => vec!["tag", "success"],
             _ => vec![]
@@ -281,6 +343,10 @@ impl PkgObject {
         &self.data().makedepends
     }
 
+    pub fn checkdepends(&self) -> &[String] {
+        &self.data().checkdepends
+    }
+
     pub fn provides(&self) -> &[String] {
         &self.data().provides
     }
@@ -301,6 +367,11 @@ impl PkgObject {
         self.data().packager.as_deref()
     }
 
+    pub fn aur_maintainer(&self) -> Option<&str> {
+        self.imp().aur_metadata.get()
+            .map(|metadata| metadata.maintainer.as_deref().unwrap_or("None (orphaned)"))
+    }
+
     pub fn build_date(&self) -> i64 {
         self.data().build_date
     }
@@ -337,10 +408,41 @@ impl PkgObject {
         self.data().has_script.as_deref()
     }
 
+    pub fn install_script(&self) -> Option<String> {
+        let data = self.data();
+
+        if !data.is_installed || data.has_script.is_none() {
+            return None;
+        }
+
+        let path = Path::new(&Pacman::config().db_path)
+            .join(format!("local/{}-{}/install", data.name, data.version));
+
+        fs::read_to_string(path).ok()
+    }
+
     pub fn validation(&self) -> PkgValidation {
         self.data().validation
     }
 
+    pub fn origin(&self) -> Option<String> {
+        let data = self.data();
+
+        if !data.is_installed {
+            return None
+        }
+
+        match &data.origin {
+            PkgOrigin::Repository => None,
+            PkgOrigin::Aur => match self.update_version() {
+                Some(version) => Some(format!("AUR (version mismatch: {version} in AUR)")),
+                None if self.update_checked() => Some(String::from("AUR (version matches)")),
+                None => Some(String::from("AUR"))
+            },
+            origin => Some(origin.to_string())
+        }
+    }
+
     //---------------------------------------
     // Alpm handle function
     //---------------------------------------
@@ -364,6 +466,46 @@ impl PkgObject {
 
             handle.replace(alpm_handle);
         });
+
+        // Directory owners are rebuilt from new handle on demand
+        Self::with_dir_owners(|dir_owners| dir_owners.replace(None));
+    }
+
+    //---------------------------------------
+    // Directory owners functions
+    //---------------------------------------
+    fn with_dir_owners<F, R>(f: F) -> R
+    where F: FnOnce(&RefCell<Option<Rc<HashMap<String, usize>>>>) -> R {
+        thread_local! {
+            static DIR_OWNERS: RefCell<Option<Rc<HashMap<String, usize>>>> = const { RefCell::new(None) };
+        }
+
+        DIR_OWNERS.with(f)
+    }
+
+    fn dir_owners() -> Rc<HashMap<String, usize>> {
+        Self::with_dir_owners(|dir_owners| {
+            let mut dir_owners = dir_owners.borrow_mut();
+
+            let dir_owners = dir_owners.get_or_insert_with(|| {
+                // Count number of local packages owning each directory
+                let mut owners: HashMap<String, usize> = HashMap::new();
+
+                Self::with_alpm_handle(|handle| {
+                    if let Some(handle) = handle.borrow().as_ref() {
+                        for pkg in handle.localdb().pkgs().iter() {
+                            for file in pkg.files().files().iter().filter(|file| file.name().ends_with(b"/")) {
+                                *owners.entry(String::from_utf8_lossy(file.name()).into_owned()).or_default() += 1;
+                            }
+                        }
+                    }
+                });
+
+                Rc::new(owners)
+            });
+
+            Rc::clone(dir_owners)
+        })
     }
 
     //---------------------------------------
@@ -428,6 +570,31 @@ impl PkgObject {
         })
     }
 
+    pub fn base_siblings(&self) -> &[String] {
+        self.imp().base_siblings.get_or_init(|| {
+            Self::with_alpm_handle(|handle| {
+                handle.borrow().as_ref()
+                    .map(|handle| {
+                        let name = self.name();
+                        let base = self.base();
+
+                        let mut siblings: Vec<String> = handle.syncdbs().iter()
+                            .chain([handle.localdb()])
+                            .flat_map(|db| db.pkgs())
+                            .filter(|pkg| pkg.base() == Some(base) && pkg.name() != name)
+                            .map(|pkg| pkg.name().to_owned())
+                            .collect();
+
+                        siblings.sort_unstable();
+                        siblings.dedup();
+
+                        siblings
+                    })
+                    .unwrap_or_default()
+            })
+        })
+    }
+
     pub fn files(&self) -> &[String] {
         self.imp().files.get_or_init(|| {
             Self::with_alpm_handle(|handle| {
@@ -481,6 +648,26 @@ impl PkgObject {
         })
     }
 
+    //---------------------------------------
+    // Sync package function
+    //---------------------------------------
+    pub fn sync_pkg(&self) -> Option<Self> {
+        // Get sync database version of installed package
+        if !self.is_installed() {
+            return None
+        }
+
+        Self::with_alpm_handle(|handle| {
+            handle.borrow().as_ref()
+                .and_then(|handle| self.alpm_sync_pkg(handle))
+                .and_then(|pkg| {
+                    let db = pkg.db()?;
+
+                    Some(Self::new(PkgData::from_alpm(pkg, false, db.name())))
+                })
+        })
+    }
+
     //---------------------------------------
     // Future properties
     //---------------------------------------
@@ -515,6 +702,97 @@ impl PkgObject {
         .await
     }
 
+    #[allow(clippy::future_not_send)]
+    pub async fn disk_usage_future(&self) -> Option<&PkgDiskUsage> {
+        self.imp().disk_usage.get_or_init(async || {
+            if !self.is_installed() {
+                return None
+            }
+
+            let files = self.files();
+
+            // Find package directories that are not shared with other packages
+            let dir_owners = Self::dir_owners();
+
+            let dirs: Vec<String> = files.iter()
+                .filter(|name| name.ends_with('/') && dir_owners.get(*name) == Some(&1))
+                .cloned()
+                .collect();
+
+            let file_names: HashSet<String> = files.iter().cloned().collect();
+
+            gio::spawn_blocking(move || {
+                let pacman_config = Pacman::config();
+
+                let mut usage = PkgDiskUsage::default();
+                let mut inodes: HashSet<(u64, u64)> = HashSet::new();
+
+                // Get size of files owned by package
+                for name in file_names.iter().filter(|name| !name.ends_with('/')) {
+                    if let Ok(metadata) = fs::symlink_metadata(pacman_config.root_dir.clone() + name)
+                        && metadata.is_file()
+                        && inodes.insert((metadata.dev(), metadata.ino())) {
+                            usage.owned_size += metadata.len() as i64;
+                        }
+                }
+
+                // Get size of untracked files in package exclusive directories
+                let root_dir = Path::new(&pacman_config.root_dir);
+
+                for dir in &dirs {
+                    for entry in WalkDir::new(root_dir.join(dir)).min_depth(1).into_iter().flatten() {
+                        let Ok(metadata) = entry.metadata() else { continue };
+
+                        if !metadata.is_file() {
+                            continue
+                        }
+
+                        let is_tracked = entry.path().strip_prefix(root_dir).ok()
+                            .and_then(Path::to_str)
+                            .is_some_and(|name| file_names.contains(name));
+
+                        if !is_tracked && inodes.insert((metadata.dev(), metadata.ino())) {
+                            usage.untracked_size += metadata.len() as i64;
+                            usage.untracked_files += 1;
+                        }
+                    }
+                }
+
+                Some(usage)
+            })
+            .await
+            .expect("Failed to complete task")
+        })
+        .await
+        .as_ref()
+    }
+
+    pub fn srcinfo(&self) -> Option<&SrcInfo> {
+        self.imp().srcinfo.get()
+    }
+
+    #[allow(clippy::future_not_send)]
+    pub async fn srcinfo_future(&self, token: CancellationToken) -> io::Result<&SrcInfo> {
+        let imp = self.imp();
+
+        if let Some(srcinfo) = imp.srcinfo.get() {
+            return Ok(srcinfo)
+        }
+
+        // Only successful downloads are cached, so failures can be retried
+        let text = PkgbuildSource::fetch(&self.repository(), self.base(), ".SRCINFO", token).await?;
+
+        let srcinfo = SrcInfo::parse(&text)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "Failed to parse .SRCINFO"))?;
+
+        if imp.srcinfo.set(srcinfo).is_ok() {
+            self.notify_has_srcinfo();
+        }
+
+        imp.srcinfo.get()
+            .ok_or_else(|| io::Error::other("Failed to store .SRCINFO"))
+    }
+
     //---------------------------------------
     // Date to string helper function
     //---------------------------------------
@@ -539,6 +817,11 @@ impl PkgObject {
         })
     }
 
+    pub fn split_optdep(optdep: &str) -> (&str, Option<&str>) {
+        optdep.split_once(':')
+            .map_or((optdep.trim(), None), |(name, description)| (name.trim(), Some(description.trim())))
+    }
+
     pub fn find_satisfier(search_term: &str, pkg_model: &gio::ListStore) -> Option<Self> {
         Self::with_alpm_handle(|handle| {
             let handle = handle.borrow();
@@ -552,4 +835,160 @@ impl PkgObject {
                 .find(|pkg| pkg.name() == db_pkg.name())
         })
     }
+
+    pub fn satisfies(&self, search_term: &str) -> bool {
+        Self::satisfies_dep(&self.name(), &self.version(), self.provides(), search_term)
+    }
+
+    pub fn satisfies_dep(name: &str, version: &str, provides: &[String], search_term: &str) -> bool {
+        let (dep_name, dep_req) = Self::split_dependency(search_term);
+
+        if name == dep_name && dep_req.is_none_or(|req| Self::version_matches(version, req)) {
+            return true
+        }
+
+        // Unversioned provides only satisfy unversioned dependencies
+        provides.iter()
+            .map(|provide| Self::split_dependency(provide))
+            .any(|(provide_name, provide_req)| {
+                provide_name == dep_name && match (dep_req, provide_req) {
+                    (None, _) => true,
+                    (Some(req), Some(("=", version))) => Self::version_matches(version, req),
+                    _ => false
+                }
+            })
+    }
+
+    pub fn conflicts_with(&self, other: &Self) -> bool {
+        self.name() != other.name() && (
+            self.conflicts().iter().any(|conflict| other.satisfies(conflict)) ||
+            other.conflicts().iter().any(|conflict| self.satisfies(conflict))
+        )
+    }
+
+    pub fn find_providers(search_term: &str, models: &[&gio::ListStore]) -> Vec<Self> {
+        let mut names: HashSet<String> = HashSet::new();
+
+        let mut providers: Vec<Self> = models.iter()
+            .flat_map(|model| model.iter::<Self>().flatten())
+            .filter(|pkg| pkg.satisfies(search_term))
+            .filter(|pkg| names.insert(pkg.name()))
+            .collect();
+
+        // Sort installed packages first, keep database order otherwise
+        providers.sort_by_key(|pkg| !pkg.is_installed());
+
+        providers
+    }
+
+    pub fn split_dependency(dep: &str) -> (&str, Option<(&str, &str)>) {
+        dep.find(['<', '>', '='])
+            .map_or((dep, None), |index| {
+                let (name, req) = dep.split_at(index);
+
+                let op_len = if req[1..].starts_with('=') { 2 } else { 1 };
+
+                (name, Some(req.split_at(op_len)))
+            })
+    }
+
+    fn version_matches(version: &str, (op, req_version): (&str, &str)) -> bool {
+        let ordering = alpm::vercmp(version, req_version);
+
+        match op {
+            "=" => ordering == Ordering::Equal,
+            ">=" => ordering != Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            "<" => ordering == Ordering::Less,
+            _ => false
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pkg(name: &str, version: &str, provides: &[&str]) -> PkgObject {
+        PkgObject::new(PkgData {
+            name: name.to_owned(),
+            version: version.to_owned(),
+            provides: provides.iter().map(|&provide| provide.to_owned()).collect(),
+            ..PkgData::default()
+        })
+    }
+
+    #[test]
+    fn split_dependency_operators() {
+        assert_eq!(PkgObject::split_dependency("glibc"), ("glibc", None));
+        assert_eq!(PkgObject::split_dependency("glibc>=2.38"), ("glibc", Some((">=", "2.38"))));
+        assert_eq!(PkgObject::split_dependency("glibc<=2.38"), ("glibc", Some(("<=", "2.38"))));
+        assert_eq!(PkgObject::split_dependency("python>3"), ("python", Some((">", "3"))));
+        assert_eq!(PkgObject::split_dependency("python<4"), ("python", Some(("<", "4"))));
+        assert_eq!(PkgObject::split_dependency("libfoo.so=2-64"), ("libfoo.so", Some(("=", "2-64"))));
+        assert_eq!(PkgObject::split_dependency("gnupg=1:2.4.0"), ("gnupg", Some(("=", "1:2.4.0"))));
+    }
+
+    #[test]
+    fn version_matches_operators() {
+        assert!(PkgObject::version_matches("2.38-1", (">=", "2.38")));
+        assert!(PkgObject::version_matches("2.39-1", (">=", "2.38")));
+        assert!(!PkgObject::version_matches("2.37-1", (">=", "2.38")));
+
+        assert!(PkgObject::version_matches("3.11.6-1", ("<", "3.12")));
+        assert!(!PkgObject::version_matches("3.12.0-1", ("<", "3.12")));
+
+        // Release is ignored if requirement has none
+        assert!(PkgObject::version_matches("1.0-2", ("=", "1.0")));
+        assert!(!PkgObject::version_matches("1.0-2", ("=", "1.0-1")));
+
+        assert!(PkgObject::version_matches("1.0-1", ("<=", "1.0")));
+        assert!(!PkgObject::version_matches("1.0-1", (">", "1.0")));
+        assert!(!PkgObject::version_matches("1.0-1", ("!=", "1.0")));
+    }
+
+    #[test]
+    fn version_matches_epochs() {
+        // Epoch takes precedence over version
+        assert!(PkgObject::version_matches("1:0.5-1", (">=", "2.0")));
+        assert!(!PkgObject::version_matches("2.0-1", (">=", "1:1.0")));
+        assert!(PkgObject::version_matches("1:2.4.0-1", ("=", "1:2.4.0")));
+        assert!(!PkgObject::version_matches("2.4.0-1", ("=", "1:2.4.0")));
+    }
+
+    #[test]
+    fn satisfies_by_name() {
+        let glibc = pkg("glibc", "2.38-7", &[]);
+
+        assert!(glibc.satisfies("glibc"));
+        assert!(glibc.satisfies("glibc>=2.38"));
+        assert!(glibc.satisfies("glibc<2.39"));
+        assert!(!glibc.satisfies("glibc>=2.39"));
+        assert!(!glibc.satisfies("glibc-locales"));
+    }
+
+    #[test]
+    fn satisfies_by_provides() {
+        let openjdk = pkg("jdk17-openjdk", "17.0.9.u9-1", &["java-runtime=17", "java-environment"]);
+
+        // Versioned provides
+        assert!(openjdk.satisfies("java-runtime"));
+        assert!(openjdk.satisfies("java-runtime>=11"));
+        assert!(openjdk.satisfies("java-runtime=17"));
+        assert!(!openjdk.satisfies("java-runtime<17"));
+
+        // Unversioned provides only satisfy unversioned dependencies
+        assert!(openjdk.satisfies("java-environment"));
+        assert!(!openjdk.satisfies("java-environment>=11"));
+
+        // Epoch in provided version
+        let gnupg = pkg("gnupg-git", "2.5.0.r1-1", &["gnupg=1:2.5.0"]);
+
+        assert!(gnupg.satisfies("gnupg>=2.6"));
+        assert!(!gnupg.satisfies("gnupg>=1:2.6"));
+    }
 }
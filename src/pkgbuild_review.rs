@@ -0,0 +1,285 @@
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+//------------------------------------------------------------------------------
+// ENUM: ReviewSeverity
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReviewSeverity {
+    Info,
+    Warning,
+    Danger,
+}
+
+impl ReviewSeverity {
+    pub const ALL: [Self; 3] = [Self::Info, Self::Warning, Self::Danger];
+
+    pub fn category(self) -> &'static str {
+        match self {
+            Self::Info => "review-info",
+            Self::Warning => "review-warning",
+            Self::Danger => "review-danger"
+        }
+    }
+
+    pub fn icon_name(self) -> &'static str {
+        match self {
+            Self::Info => "dialog-information-symbolic",
+            Self::Warning => "dialog-warning-symbolic",
+            Self::Danger => "dialog-error-symbolic"
+        }
+    }
+
+    pub fn css_class(self) -> &'static str {
+        match self {
+            Self::Info => "accent",
+            Self::Warning => "warning",
+            Self::Danger => "error"
+        }
+    }
+
+    pub fn color(self) -> &'static str {
+        match self {
+            Self::Info => "rgba(53, 132, 228, 0.15)",
+            Self::Warning => "rgba(229, 165, 10, 0.2)",
+            Self::Danger => "rgba(224, 27, 36, 0.2)"
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: ReviewFinding
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct ReviewFinding {
+    pub line: usize,
+    pub severity: ReviewSeverity,
+    pub message: String
+}
+
+impl ReviewFinding {
+    fn new(line: usize, severity: ReviewSeverity, message: &str) -> Self {
+        Self {
+            line,
+            severity,
+            message: message.to_owned()
+        }
+    }
+
+    //---------------------------------------
+    // Scan PKGBUILD function
+    //---------------------------------------
+    pub fn scan(pkgbuild: &str) -> Vec<Self> {
+        static RULES: LazyLock<Vec<(Regex, ReviewSeverity, &str)>> = LazyLock::new(|| {
+            [
+                (r"\b(?:curl|wget)\b[^|#]*\|\s*(?:sudo\s+)?(?:ba|z|da|k)?sh\b", ReviewSeverity::Danger, "Downloaded script is piped to a shell"),
+                (r"\bbase64\s+(?:-d|--decode)\b", ReviewSeverity::Danger, "Decodes base64 data"),
+                (r"\beval\b", ReviewSeverity::Warning, "Uses eval"),
+                (r"\bhttp://", ReviewSeverity::Warning, "Downloads over plain HTTP"),
+                (r"^\s*install\s*=", ReviewSeverity::Info, "Package runs an install script"),
+            ]
+            .into_iter()
+            .map(|(expr, severity, message)| (Regex::new(expr).expect("Failed to compile Regex"), severity, message))
+            .collect()
+        });
+
+        static ENCODED_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"[A-Za-z0-9+/]{80,}={0,2}")
+                .expect("Failed to compile Regex")
+        });
+
+        static WRITE_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r#"(?:>>?|\b(?:install|cp|mv|mkdir|ln|touch|rm|chmod|chown|tee)\b[^#]*\s)["']?(?:/(?:usr|etc|opt|var|home|root|boot|lib|lib64|bin|sbin|srv)\b|~/|\$HOME\b|\$\{HOME\})"#)
+                .expect("Failed to compile Regex")
+        });
+
+        static SUDO_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"(?:^|[;&|{}`]|[^=]\(|\b(?:then|do|else)\b)\s*sudo\b")
+                .expect("Failed to compile Regex")
+        });
+
+        static ARRAY_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"^\s*\w+\+?=\(")
+                .expect("Failed to compile Regex")
+        });
+
+        static SUMS_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"^\s*(?:md5|sha1|sha224|sha256|sha384|sha512|b2|ck)sums(?:_\w+)?\s*=")
+                .expect("Failed to compile Regex")
+        });
+
+        static SOURCE_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"^\s*source(?:_\w+)?\s*=")
+                .expect("Failed to compile Regex")
+        });
+
+        let mut findings = vec![];
+
+        let mut source_line = None;
+        let mut has_sums = false;
+        let mut has_vcs_source = false;
+        let mut in_sums = false;
+        let mut in_array = false;
+
+        for (index, line) in pkgbuild.lines().enumerate() {
+            let code = Self::strip_comment(line);
+
+            if code.trim().is_empty() {
+                continue;
+            }
+
+            // Track source and checksum arrays
+            if SOURCE_EXPR.is_match(code) {
+                source_line.get_or_insert(index);
+            }
+
+            if ["git+", "svn+", "hg+", "bzr+"].iter().any(|vcs| code.contains(vcs)) {
+                has_vcs_source = true;
+            }
+
+            if SUMS_EXPR.is_match(code) {
+                has_sums = true;
+                in_sums = true;
+            }
+
+            if in_sums {
+                if code.split(['(', ')', '\'', '"', ' ', '\t']).any(|word| word == "SKIP") {
+                    let severity = if has_vcs_source { ReviewSeverity::Info } else { ReviewSeverity::Warning };
+
+                    findings.push(Self::new(index, severity, "Checksum verification is skipped"));
+                }
+
+                if code.contains(')') {
+                    in_sums = false;
+                }
+
+                continue;
+            }
+
+            // Track multi-line arrays (e.g. depends) where words are not commands
+            let array_start = ARRAY_EXPR.is_match(code);
+
+            let is_command = !in_array && !array_start;
+
+            if array_start || in_array {
+                in_array = !code.contains(')');
+            }
+
+            // Match line rules
+            for (expr, severity, message) in RULES.iter() {
+                if expr.is_match(code) {
+                    findings.push(Self::new(index, *severity, message));
+                }
+            }
+
+            if is_command && SUDO_EXPR.is_match(code) {
+                findings.push(Self::new(index, ReviewSeverity::Danger, "Uses sudo"));
+            }
+
+            if ENCODED_EXPR.is_match(code) {
+                findings.push(Self::new(index, ReviewSeverity::Danger, "Contains a long encoded string"));
+            }
+
+            if WRITE_EXPR.is_match(code) && !code.contains("pkgdir") {
+                findings.push(Self::new(index, ReviewSeverity::Danger, "Writes outside $pkgdir"));
+            }
+        }
+
+        if let Some(line) = source_line && !has_sums {
+            findings.push(Self::new(line, ReviewSeverity::Warning, "Sources have no checksums"));
+        }
+
+        findings.sort_by_key(|finding| finding.line);
+
+        findings
+    }
+
+    //---------------------------------------
+    // Strip comment function
+    //---------------------------------------
+    fn strip_comment(line: &str) -> &str {
+        let mut quote: Option<char> = None;
+        let mut escaped = false;
+        let mut word_start = true;
+
+        for (index, c) in line.char_indices() {
+            if escaped {
+                escaped = false;
+                word_start = false;
+                continue;
+            }
+
+            match (quote, c) {
+                // No escapes inside single quotes
+                (Some('\''), '\'') => quote = None,
+                (Some('\''), _) => {},
+                (_, '\\') => escaped = true,
+                (Some(_), '"') => quote = None,
+                (Some(_), _) => {},
+                (None, '\'' | '"') => quote = Some(c),
+                // '#' only starts a comment at the beginning of a word
+                (None, '#') if word_start => return &line[..index],
+                _ => {}
+            }
+
+            word_start = c.is_whitespace() || matches!(c, ';' | '&' | '|' | '(');
+        }
+
+        line
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(pkgbuild: &str) -> Vec<(usize, ReviewSeverity, String)> {
+        ReviewFinding::scan(pkgbuild).into_iter()
+            .map(|finding| (finding.line, finding.severity, finding.message))
+            .collect()
+    }
+
+    #[test]
+    fn sudo_in_command_position() {
+        let pkgbuild = "package() {\n  sudo make install\n  make && sudo cp foo /tmp\n  out=$(sudo id)\n}";
+
+        let lines: Vec<usize> = messages(pkgbuild).into_iter()
+            .filter(|(_, severity, message)| *severity == ReviewSeverity::Danger && message == "Uses sudo")
+            .map(|(line, _, _)| line)
+            .collect();
+
+        assert_eq!(lines, [1, 2, 3]);
+    }
+
+    #[test]
+    fn sudo_as_dependency_is_not_reported() {
+        let pkgbuild = "depends=('sudo' 'bash')\nmakedepends=(sudo)\noptdepends=(\n  'sudo: run as root'\n  sudo\n)\necho sudo";
+
+        assert!(messages(pkgbuild).iter().all(|(_, _, message)| message != "Uses sudo"));
+    }
+
+    #[test]
+    fn hash_inside_quotes_is_not_a_comment() {
+        assert_eq!(ReviewFinding::strip_comment("echo \"value #1\" # comment"), "echo \"value #1\" ");
+        assert_eq!(ReviewFinding::strip_comment("echo 'a # b' && ls"), "echo 'a # b' && ls");
+        assert_eq!(ReviewFinding::strip_comment("echo ${var#prefix} #x"), "echo ${var#prefix} ");
+        assert_eq!(ReviewFinding::strip_comment("echo \\# not a comment"), "echo \\# not a comment");
+        assert_eq!(ReviewFinding::strip_comment("# full line"), "");
+
+        let pkgbuild = "build() {\n  echo \"step #1\" && curl https://example.com/x.sh | sh\n}";
+
+        assert!(messages(pkgbuild).iter()
+            .any(|(line, severity, _)| *line == 1 && *severity == ReviewSeverity::Danger));
+    }
+
+    #[test]
+    fn trailing_comment_is_ignored() {
+        let pkgbuild = "build() {\n  make # sudo make install\n}";
+
+        assert!(messages(pkgbuild).is_empty());
+    }
+}
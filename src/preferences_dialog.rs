@@ -11,6 +11,7 @@ use crate::{
     APP_ID,
     window::PacViewWindow,
     search_bar::SearchProp,
+    aur_helper::AurHelper,
     utils::StyleSchemes,
 };
 
@@ -45,6 +46,10 @@ mod imp {
         #[template_child]
         pub(super) infopane_width_row: TemplateChild<adw::SpinRow>,
         #[template_child]
+        pub(super) aur_helper_row: TemplateChild<adw::ComboRow>,
+        #[template_child]
+        pub(super) aur_url_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
         pub(super) aur_database_download_row: TemplateChild<adw::ExpanderRow>,
         #[template_child]
         pub(super) aur_database_download_switch: TemplateChild<gtk::Switch>,
@@ -75,12 +80,20 @@ mod imp {
         #[template_child]
         pub(super) pkgbuild_custom_font_row: TemplateChild<adw::ActionRow>,
         #[template_child]
+        pub(super) pkgbuild_repo_url_row: TemplateChild<adw::EntryRow>,
+        #[template_child]
+        pub(super) pkgbuild_clone_dir_row: TemplateChild<adw::ActionRow>,
+        #[template_child]
         pub(super) reset_button: TemplateChild<adw::ButtonRow>,
 
         #[property(get, set, builder(ColorScheme::default()))]
         color_scheme: Cell<ColorScheme>,
         #[property(get, set)]
         infopane_width: Cell<f64>,
+        #[property(get, set, builder(AurHelper::default()))]
+        aur_helper: Cell<AurHelper>,
+        #[property(get, set)]
+        aur_url: RefCell<String>,
         #[property(get, set)]
         aur_database_download: Cell<bool>,
         #[property(get, set)]
@@ -107,6 +120,10 @@ mod imp {
         pkgbuild_use_system_font: Cell<bool>,
         #[property(get, set)]
         pkgbuild_custom_font: RefCell<String>,
+        #[property(get, set)]
+        pkgbuild_repo_url: RefCell<String>,
+        #[property(get, set)]
+        pkgbuild_clone_dir: RefCell<String>,
     }
 
     //---------------------------------------
@@ -200,6 +217,11 @@ impl PreferencesDialog {
             style_manager.set_color_scheme(color_scheme);
         });
 
+        // AUR helper property notify signal
+        self.connect_aur_helper_notify(|dialog| {
+            dialog.notify_pkgbuild_clone_dir();
+        });
+
         // PKGBUILD custom font row activated signal
         imp.pkgbuild_custom_font_row.connect_activated(clone!(
             #[weak(rename_to = dialog)] self,
@@ -222,6 +244,32 @@ impl PreferencesDialog {
             }
         ));
 
+        // PKGBUILD clone directory row activated signal
+        imp.pkgbuild_clone_dir_row.connect_activated(clone!(
+            #[weak(rename_to = dialog)] self,
+            move |_| {
+                let file_dialog = gtk::FileDialog::builder()
+                    .modal(true)
+                    .title("Select Folder")
+                    .build();
+
+                if !dialog.pkgbuild_clone_dir().is_empty() {
+                    file_dialog.set_initial_folder(Some(&gio::File::for_path(dialog.pkgbuild_clone_dir())));
+                }
+
+                file_dialog.select_folder(
+                    dialog.root().and_downcast_ref::<PacViewWindow>(),
+                    None::<&gio::Cancellable>,
+                    clone!(move |response| {
+                        if let Ok(folder) = response
+                            && let Some(path) = folder.path() {
+                                dialog.set_pkgbuild_clone_dir(path.display().to_string());
+                            }
+                    })
+                );
+            }
+        ));
+
         // Preferences reset button clicked signal
         imp.reset_button.connect_activated(clone!(
             #[weak(rename_to = dialog)] self,
@@ -244,6 +292,8 @@ impl PreferencesDialog {
 
                             settings.reset("color-scheme");
                             settings.reset("infopane-width");
+                            settings.reset("aur-helper");
+                            settings.reset("aur-url");
                             settings.reset("aur-database-download");
                             settings.reset("aur-database-age");
                             settings.reset("auto-refresh");
@@ -257,6 +307,8 @@ impl PreferencesDialog {
                             settings.reset("pkgbuild-style-scheme");
                             settings.reset("pkgbuild-use-system-font");
                             settings.reset("pkgbuild-custom-font");
+                            settings.reset("pkgbuild-repo-url");
+                            settings.reset("pkgbuild-clone-dir");
                         }
                     }
                 );
@@ -286,6 +338,16 @@ impl PreferencesDialog {
             .bidirectional()
             .build();
 
+        self.bind_property("aur-helper", &imp.aur_helper_row.get(), "selected")
+            .sync_create()
+            .bidirectional()
+            .build();
+
+        self.bind_property("aur-url", &imp.aur_url_row.get(), "text")
+            .sync_create()
+            .bidirectional()
+            .build();
+
         self.bind_property("aur-database-download", &imp.aur_database_download_row.get(), "expanded")
             .sync_create()
             .bidirectional()
@@ -377,6 +439,23 @@ impl PreferencesDialog {
             .sync_create()
             .bidirectional()
             .build();
+
+        self.bind_property("pkgbuild-repo-url", &imp.pkgbuild_repo_url_row.get(), "text")
+            .sync_create()
+            .bidirectional()
+            .build();
+
+        self.bind_property("pkgbuild-clone-dir", &imp.pkgbuild_clone_dir_row.get(), "subtitle")
+            .transform_to(|binding, dir: String| {
+                let helper = binding.source()
+                    .and_downcast::<Self>()
+                    .map(|dialog| dialog.aur_helper())
+                    .unwrap_or_default();
+
+                Some(if dir.is_empty() { format!("{} clone directory", helper.as_ref()) } else { dir })
+            })
+            .sync_create()
+            .build();
     }
 }
 
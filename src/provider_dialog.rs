@@ -0,0 +1,135 @@
+use std::sync::OnceLock;
+
+use gtk::glib;
+use adw::{prelude::*, subclass::prelude::*};
+use glib::clone;
+use glib::subclass::Signal;
+
+use crate::pkg_object::PkgObject;
+
+//------------------------------------------------------------------------------
+// MODULE: ProviderDialog
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate)]
+    #[template(resource = "/com/github/PacView/ui/provider_dialog.ui")]
+    pub struct ProviderDialog {
+        #[template_child]
+        pub(super) providers_group: TemplateChild<adw::PreferencesGroup>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for ProviderDialog {
+        const NAME: &'static str = "ProviderDialog";
+        type Type = super::ProviderDialog;
+        type ParentType = adw::Dialog;
+
+        fn class_init(klass: &mut Self::Class) {
+            klass.bind_template();
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    impl ObjectImpl for ProviderDialog {
+        //---------------------------------------
+        // Signals
+        //---------------------------------------
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+            SIGNALS.get_or_init(|| {
+                vec![
+                    Signal::builder("provider-selected")
+                        .param_types([PkgObject::static_type()])
+                        .build(),
+                ]
+            })
+        }
+    }
+
+    impl WidgetImpl for ProviderDialog {}
+    impl AdwDialogImpl for ProviderDialog {}
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: ProviderDialog
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct ProviderDialog(ObjectSubclass<imp::ProviderDialog>)
+    @extends adw::Dialog, gtk::Widget,
+    @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::ShortcutManager;
+}
+
+impl ProviderDialog {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    pub fn new(dependency: &str, providers: &[PkgObject]) -> Self {
+        let dialog: Self = glib::Object::builder()
+            .property("title", format!("Providers of {dependency}"))
+            .build();
+
+        let group = &dialog.imp().providers_group;
+
+        group.set_description(Some(&format!("{} packages provide {dependency}", providers.len())));
+
+        // Add provider rows
+        for pkg in providers {
+            let mut subtitle = format!("{} {}", pkg.repository(), pkg.version());
+
+            // List other providers that cannot be installed alongside this one
+            let conflicts: Vec<String> = providers.iter()
+                .filter(|other| pkg.conflicts_with(other))
+                .map(|other| other.name())
+                .collect();
+
+            if !conflicts.is_empty() {
+                subtitle.push_str(&format!("\nConflicts with: {}", conflicts.join(", ")));
+            }
+
+            let row = adw::ActionRow::builder()
+                .title(pkg.name())
+                .subtitle(subtitle)
+                .activatable(true)
+                .build();
+
+            if pkg.is_installed() {
+                let label = gtk::Label::builder()
+                    .label("Installed")
+                    .valign(gtk::Align::Center)
+                    .build();
+
+                label.set_css_classes(&pkg.status_css_classes());
+
+                row.add_suffix(&label);
+            }
+
+            row.add_suffix(&gtk::Image::from_icon_name("go-next-symbolic"));
+
+            let pkg = pkg.clone();
+
+            row.connect_activated(clone!(
+                #[weak] dialog,
+                #[strong] pkg,
+                move |_| {
+                    dialog.emit_by_name::<()>("provider-selected", &[&pkg]);
+                    dialog.close();
+                }
+            ));
+
+            group.add(&row);
+        }
+
+        dialog
+    }
+}
@@ -0,0 +1,76 @@
+use std::cell::RefCell;
+
+use gtk::glib;
+use gtk::subclass::prelude::*;
+use gtk::prelude::ObjectExt;
+
+//------------------------------------------------------------------------------
+// MODULE: RollbackObject
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::RollbackObject)]
+    pub struct RollbackObject {
+        #[property(get, set, construct_only)]
+        package: RefCell<String>,
+        #[property(get, set, construct_only)]
+        action: RefCell<String>,
+        #[property(get, set, construct_only)]
+        current_version: RefCell<String>,
+        #[property(get, set, construct_only)]
+        target_version: RefCell<String>,
+        #[property(get, set, construct_only)]
+        cache_file: RefCell<String>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for RollbackObject {
+        const NAME: &'static str = "RollbackObject";
+        type Type = super::RollbackObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for RollbackObject {}
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: RollbackObject
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct RollbackObject(ObjectSubclass<imp::RollbackObject>);
+}
+
+impl RollbackObject {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    pub fn new(package: &str, action: &str, current_version: Option<&str>, target_version: Option<&str>, cache_file: Option<&str>) -> Self {
+        // Build RollbackObject
+        glib::Object::builder()
+            .property("package", package)
+            .property("action", action)
+            .property("current-version", current_version.unwrap_or_default())
+            .property("target-version", target_version.unwrap_or_default())
+            .property("cache-file", cache_file.unwrap_or_default())
+            .build()
+    }
+
+    //---------------------------------------
+    // Public functions
+    //---------------------------------------
+    pub fn is_removal(&self) -> bool {
+        self.target_version().is_empty()
+    }
+
+    pub fn is_available(&self) -> bool {
+        self.is_removal() || !self.cache_file().is_empty()
+    }
+}
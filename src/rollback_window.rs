@@ -0,0 +1,381 @@
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::cmp::Ordering;
+use std::fmt::Write as _;
+
+use gtk::{glib, gio, gdk};
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use glib::clone;
+use gdk::{Key, ModifierType};
+
+use crate::{
+    pkg_object::PkgObject,
+    log_object::PkgEvent,
+    rollback_object::RollbackObject,
+    utils::Pacman
+};
+
+//------------------------------------------------------------------------------
+// MODULE: RollbackWindow
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate, glib::Properties)]
+    #[properties(wrapper_type = super::RollbackWindow)]
+    #[template(resource = "/com/github/PacView/ui/rollback_window.ui")]
+    pub struct RollbackWindow {
+        #[template_child]
+        pub(super) date_button: TemplateChild<gtk::MenuButton>,
+        #[template_child]
+        pub(super) calendar: TemplateChild<gtk::Calendar>,
+
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) view: TemplateChild<gtk::ColumnView>,
+        #[template_child]
+        pub(super) model: TemplateChild<gio::ListStore>,
+        #[template_child]
+        pub(super) selection: TemplateChild<gtk::NoSelection>,
+
+        #[template_child]
+        pub(super) footer_label: TemplateChild<gtk::Label>,
+
+        #[property(get, set)]
+        is_loaded: Cell<bool>,
+
+        pub(super) events: RefCell<Vec<PkgEvent>>,
+        pub(super) installed: RefCell<HashMap<String, String>>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for RollbackWindow {
+        const NAME: &'static str = "RollbackWindow";
+        type Type = super::RollbackWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            RollbackObject::ensure_type();
+
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+
+            // Add key bindings
+            Self::bind_shortcuts(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for RollbackWindow {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+        }
+    }
+
+    impl WidgetImpl for RollbackWindow {}
+    impl WindowImpl for RollbackWindow {}
+    impl AdwWindowImpl for RollbackWindow {}
+
+    impl RollbackWindow {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Select date action
+            klass.install_action("rollback.select-date", None, |window, _, _| {
+                window.imp().date_button.popup();
+            });
+
+            // Copy commands action
+            klass.install_action("rollback.copy-commands", None, |window, _, _| {
+                window.clipboard().set_text(&window.commands());
+            });
+
+            // Copy list action
+            klass.install_action("rollback.copy", None, |window, _, _| {
+                let mut output = format!("## Rollback to {}\n|Package|Action|Current Version|Target Version|Cache File|\n|---|---|---|---|---|\n", window.selected_date());
+
+                for item in window.imp().selection.iter::<glib::Object>()
+                    .flatten()
+                    .filter_map(|item| item.downcast::<RollbackObject>().ok()) {
+                        writeln!(output, "|{package}|{action}|{current}|{target}|{file}|",
+                            package=item.package(),
+                            action=item.action(),
+                            current=item.current_version(),
+                            target=item.target_version(),
+                            file=item.cache_file()
+                        )
+                        .unwrap();
+                    }
+
+                window.clipboard().set_text(&output);
+            });
+        }
+
+        //---------------------------------------
+        // Bind shortcuts
+        //---------------------------------------
+        fn bind_shortcuts(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Close window binding
+            klass.add_binding_action(Key::Escape, ModifierType::NO_MODIFIER_MASK, "window.close");
+
+            // Date key binding
+            klass.add_binding_action(Key::D, ModifierType::CONTROL_MASK, "rollback.select-date");
+
+            // Copy key bindings
+            klass.add_binding_action(Key::C, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "rollback.copy");
+            klass.add_binding_action(Key::R, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "rollback.copy-commands");
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: RollbackWindow
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct RollbackWindow(ObjectSubclass<imp::RollbackWindow>)
+        @extends adw::Window, gtk::Window, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Native, gtk::Root, gtk::ShortcutManager;
+}
+
+impl RollbackWindow {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        let imp = self.imp();
+
+        // Calendar day selected signal
+        imp.calendar.connect_day_selected(clone!(
+            #[weak(rename_to = window)] self,
+            move |_| {
+                window.imp().date_button.popdown();
+
+                window.update_view();
+            }
+        ));
+
+        // Model items changed signal
+        imp.model.connect_items_changed(clone!(
+            #[weak(rename_to = window)] self,
+            move |model, _, _, _| {
+                let imp = window.imp();
+
+                let n_items = model.n_items();
+
+                imp.stack.set_visible_child_name(if n_items == 0 { "empty" } else { "view" });
+
+                window.action_set_enabled("rollback.copy", n_items > 0);
+                window.action_set_enabled("rollback.copy-commands", n_items > 0);
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Selected date helper function
+    //---------------------------------------
+    fn selected_date(&self) -> String {
+        self.imp().calendar.date().format("%Y-%m-%d")
+            .map(|date| date.to_string())
+            .unwrap_or_default()
+    }
+
+    //---------------------------------------
+    // Cache file helper function
+    //---------------------------------------
+    fn find_cache_file(name: &str, version: &str) -> Option<String> {
+        let prefix = format!("{name}-{version}-");
+
+        Pacman::cache().read().unwrap().iter()
+            .find(|path| {
+                path.file_name()
+                    .and_then(|file_name| file_name.to_str())
+                    .and_then(|file_name| file_name.strip_prefix(&prefix))
+                    .is_some_and(|arch| arch.split_once(".pkg.tar").is_some_and(|(arch, _)| !arch.contains('-')))
+            })
+            .map(|path| path.display().to_string())
+    }
+
+    //---------------------------------------
+    // Update view
+    //---------------------------------------
+    fn update_view(&self) {
+        let imp = self.imp();
+
+        let date = self.selected_date();
+
+        imp.date_button.set_label(&date);
+
+        // Replay package events up to and including selected date
+        let mut state: HashMap<&str, Option<&str>> = HashMap::new();
+
+        let events = imp.events.borrow();
+
+        for event in events.iter().take_while(|event| event.date <= date) {
+            state.insert(&event.name, event.new_version());
+        }
+
+        // Compare with currently installed packages
+        let installed = imp.installed.borrow();
+
+        let mut items: Vec<RollbackObject> = state.iter()
+            .filter_map(|(&name, &target)| {
+                let current = installed.get(name).map(String::as_str);
+
+                match (current, target) {
+                    (Some(current), Some(target)) if current != target => {
+                        let action = if alpm::vercmp(current, target) == Ordering::Greater { "downgrade" } else { "upgrade" };
+
+                        Some(RollbackObject::new(name, action, Some(current), Some(target), Self::find_cache_file(name, target).as_deref()))
+                    },
+                    (None, Some(target)) => {
+                        Some(RollbackObject::new(name, "install", None, Some(target), Self::find_cache_file(name, target).as_deref()))
+                    },
+                    (Some(current), None) => {
+                        Some(RollbackObject::new(name, "remove", Some(current), None, None))
+                    },
+                    _ => None
+                }
+            })
+            .collect();
+
+        // Packages first installed after selected date
+        items.extend(installed.iter()
+            .filter(|(name, _)| !state.contains_key(name.as_str()))
+            .filter(|(name, _)| {
+                events.iter()
+                    .find(|event| &event.name == *name)
+                    .is_some_and(|event| event.action == "installed" && event.date > date)
+            })
+            .map(|(name, current)| RollbackObject::new(name, "remove", Some(current), None, None))
+        );
+
+        items.sort_unstable_by_key(RollbackObject::package);
+
+        let n_missing = items.iter().filter(|item| !item.is_available()).count();
+
+        imp.footer_label.set_label(&format!("{} package change{} required, {n_missing} version{} not available in cache",
+            items.len(),
+            if items.len() == 1 { "" } else { "s" },
+            if n_missing == 1 { "" } else { "s" }
+        ));
+
+        imp.model.splice(0, imp.model.n_items(), &items);
+    }
+
+    //---------------------------------------
+    // Rollback commands
+    //---------------------------------------
+    fn commands(&self) -> String {
+        let items: Vec<RollbackObject> = self.imp().model.iter::<RollbackObject>()
+            .flatten()
+            .collect();
+
+        let mut output = format!("# Restore packages to state on {}\n", self.selected_date());
+
+        let files: Vec<String> = items.iter()
+            .filter(|item| !item.is_removal() && item.is_available())
+            .map(RollbackObject::cache_file)
+            .collect();
+
+        if !files.is_empty() {
+            writeln!(output, "sudo pacman -U {}", files.join(" ")).unwrap();
+        }
+
+        let removals: Vec<String> = items.iter()
+            .filter(|item| item.is_removal())
+            .map(RollbackObject::package)
+            .collect();
+
+        if !removals.is_empty() {
+            writeln!(output, "sudo pacman -R {}", removals.join(" ")).unwrap();
+        }
+
+        for item in items.iter().filter(|item| !item.is_available()) {
+            writeln!(output, "# Not in cache: {}-{}", item.package(), item.target_version()).unwrap();
+        }
+
+        output
+    }
+
+    //---------------------------------------
+    // Populate window
+    //---------------------------------------
+    fn populate(&self, pkg_model: &gio::ListStore) {
+        let imp = self.imp();
+
+        let installed: HashMap<String, String> = pkg_model.iter::<PkgObject>()
+            .flatten()
+            .filter(PkgObject::is_installed)
+            .map(|pkg| (pkg.name(), pkg.version()))
+            .collect();
+
+        imp.installed.replace(installed);
+
+        glib::spawn_future_local(clone!(
+            #[weak(rename_to = window)] self,
+            async move {
+                let events = gio::spawn_blocking(|| {
+                    Pacman::log().read().unwrap().as_ref()
+                        .map(|log| PkgEvent::from_log(log))
+                        .unwrap_or_default()
+                })
+                .await
+                .expect("Failed to complete task");
+
+                window.imp().events.replace(events);
+
+                window.update_view();
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Show window
+    //---------------------------------------
+    pub fn show(&self, pkg_model: &gio::ListStore) {
+        self.present();
+
+        glib::idle_add_local_once(clone!(
+            #[weak(rename_to = window)] self,
+            #[weak] pkg_model,
+            move || {
+                if !window.is_loaded() {
+                    window.populate(&pkg_model);
+
+                    window.set_is_loaded(true);
+                }
+            }
+        ));
+    }
+}
+
+impl Default for RollbackWindow {
+    //---------------------------------------
+    // Default constructor
+    //---------------------------------------
+    fn default() -> Self {
+        glib::Object::builder().build()
+    }
+}
@@ -1,8 +1,9 @@
 use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::fmt::Write as _;
+use std::io;
 
-use futures::io;
 use gtk::{gio, glib, gdk, pango};
 use adw::subclass::prelude::*;
 use gtk::prelude::*;
@@ -16,9 +17,16 @@ use tokio_util::sync::CancellationToken;
 use crate::{
     APP_ID,
     pkg_object::PkgObject,
-    utils::{StyleSchemes, Paths, TokioUtils}
+    pkgbuild_review::{ReviewFinding, ReviewSeverity},
+    line_diff::{DiffRow, DiffKind},
+    utils::{StyleSchemes, PkgbuildSource}
 };
 
+//------------------------------------------------------------------------------
+// CONST variables
+//------------------------------------------------------------------------------
+const INSTALLED_SCRIPT: &str = ".INSTALL";
+
 //------------------------------------------------------------------------------
 // MODULE: SourceWindow
 //------------------------------------------------------------------------------
@@ -32,6 +40,10 @@ mod imp {
     #[properties(wrapper_type = super::SourceWindow)]
     #[template(resource = "/com/github/PacView/ui/source_window.ui")]
     pub struct SourceWindow {
+        #[template_child]
+        pub(super) file_dropdown: TemplateChild<gtk::DropDown>,
+        #[template_child]
+        pub(super) file_model: TemplateChild<gtk::StringList>,
         #[template_child]
         pub(super) stack: TemplateChild<gtk::Stack>,
         #[template_child]
@@ -40,17 +52,62 @@ mod imp {
         pub(super) source_view: TemplateChild<sourceview5::View>,
         #[template_child]
         pub(super) error_status: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub(super) split_view: TemplateChild<adw::OverlaySplitView>,
+        #[template_child]
+        pub(super) review_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub(super) review_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) review_stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) review_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub(super) diff_button: TemplateChild<gtk::ToggleButton>,
+        #[template_child]
+        pub(super) old_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) old_scroll: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub(super) old_buffer: TemplateChild<sourceview5::Buffer>,
+        #[template_child]
+        pub(super) new_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub(super) new_scroll: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
+        pub(super) new_buffer: TemplateChild<sourceview5::Buffer>,
 
         #[property(get = Self::buffer)]
         buffer: PhantomData<sourceview5::Buffer>,
         #[property(get, set, construct_only)]
         pkg_name: RefCell<String>,
+        #[property(get, set, construct_only)]
+        pkg_base: RefCell<String>,
+        #[property(get, set, construct_only)]
+        repository: RefCell<String>,
         #[property(get, set, nullable, construct_only)]
         pkgbuild_url: RefCell<Option<String>>,
+        #[property(get, set, nullable, construct_only)]
+        installed_version: RefCell<Option<String>>,
+        #[property(get, set, nullable, construct_only)]
+        update_version: RefCell<Option<String>>,
+        #[property(get, set, nullable, construct_only)]
+        install_script: RefCell<Option<String>>,
         #[property(get, set)]
         loading: Cell<bool>,
+        #[property(get, set)]
+        review: Cell<bool>,
+        #[property(get, set)]
+        diff: Cell<bool>,
+
+        pub(super) pkg: glib::WeakRef<PkgObject>,
 
         pub(super) cancel_token: RefCell<Option<CancellationToken>>,
+        pub(super) list_token: RefCell<Option<CancellationToken>>,
+        pub(super) srcinfo_token: RefCell<Option<CancellationToken>>,
+        pub(super) files: RefCell<HashMap<String, String>>,
+        pub(super) review_findings: RefCell<Vec<ReviewFinding>>,
+        pub(super) diff_loaded: Cell<bool>,
     }
 
     //---------------------------------------
@@ -90,7 +147,22 @@ mod imp {
     }
 
     impl WidgetImpl for SourceWindow {}
-    impl WindowImpl for SourceWindow {}
+    impl WindowImpl for SourceWindow {
+        //---------------------------------------
+        // Window close handler
+        //---------------------------------------
+        fn close_request(&self) -> glib::Propagation {
+            let obj = self.obj();
+
+            obj.cancel_download();
+
+            if let Some(token) = self.srcinfo_token.take() {
+                token.cancel();
+            }
+
+            self.parent_close_request()
+        }
+    }
     impl AdwWindowImpl for SourceWindow {}
 
     impl SourceWindow {
@@ -100,10 +172,13 @@ mod imp {
         fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
             // Save action
             klass.install_action_async("source.save", None, async |window, _, _| {
+                let file_name = window.selected_file()
+                    .unwrap_or_else(|| String::from("PKGBUILD"));
+
                 let file_dialog = gtk::FileDialog::builder()
                     .modal(true)
-                    .title("Save PKGBUILD")
-                    .initial_name("PKGBUILD")
+                    .title(format!("Save {file_name}"))
+                    .initial_name(file_name)
                     .build();
 
                 let response = file_dialog.save_future(Some(&window)).await;
@@ -134,10 +209,19 @@ mod imp {
                 }
             });
 
+            // Review property action
+            klass.install_property_action("source.review", "review");
+
+            // Diff property action
+            klass.install_property_action("source.diff", "diff");
+
             // Refresh action
             klass.install_action_async("source.refresh", None, async |window, _, _| {
                 window.cancel_download();
 
+                window.set_diff(false);
+                window.imp().diff_loaded.set(false);
+
                 if !window.loading() {
                     window.download_pkgbuild().await;
                 }
@@ -159,6 +243,12 @@ mod imp {
 
             // Refresh binding
             klass.add_binding_action(Key::F5, ModifierType::NO_MODIFIER_MASK, "source.refresh");
+
+            // Review binding
+            klass.add_binding_action(Key::R, ModifierType::CONTROL_MASK, "source.review");
+
+            // Diff binding
+            klass.add_binding_action(Key::D, ModifierType::CONTROL_MASK, "source.diff");
         }
 
         //---------------------------------------
@@ -188,12 +278,27 @@ impl SourceWindow {
     pub fn new(parent: &impl IsA<gtk::Window>, pkg: &PkgObject) -> Self {
         let pkg_name = pkg.name();
 
-        glib::Object::builder()
+        // Installed version only needed to compare against pending update
+        let (installed_version, update_version) = match pkg.update_version() {
+            Some(update_version) => (Some(pkg.version()), Some(update_version)),
+            None => (None, None)
+        };
+
+        let window: Self = glib::Object::builder()
             .property("transient-for", parent)
             .property("title", format!("{}  \u{2022}  PKGBUILD", &pkg_name))
             .property("pkg-name", pkg_name)
+            .property("pkg-base", pkg.base())
+            .property("repository", pkg.repository())
             .property("pkgbuild-url", pkg.pkgbuild_url())
-            .build()
+            .property("installed-version", installed_version)
+            .property("update-version", update_version)
+            .property("install-script", pkg.install_script())
+            .build();
+
+        window.imp().pkg.set(Some(pkg));
+
+        window
     }
 
     //---------------------------------------
@@ -206,7 +311,11 @@ impl SourceWindow {
 
         let scheme = StyleSchemes::scheme(&id, style_manager.is_dark());
 
+        let imp = self.imp();
+
         self.buffer().set_style_scheme(scheme.as_ref());
+        imp.old_buffer.set_style_scheme(scheme.as_ref());
+        imp.new_buffer.set_style_scheme(scheme.as_ref());
     }
 
     //-----------------------------------
@@ -282,7 +391,13 @@ impl SourceWindow {
     // Cancel download function
     //---------------------------------------
     fn cancel_download(&self) {
-        if let Some(token) = self.imp().cancel_token.take() {
+        let imp = self.imp();
+
+        if let Some(token) = imp.cancel_token.take() {
+            token.cancel();
+        }
+
+        if let Some(token) = imp.list_token.take() {
             token.cancel();
         }
     }
@@ -296,6 +411,7 @@ impl SourceWindow {
 
         imp.stack.set_visible_child_name("loading");
         self.action_set_enabled("source.save", false);
+        self.action_set_enabled("source.diff", false);
 
         // Set loading property
         self.set_loading(true);
@@ -307,39 +423,180 @@ impl SourceWindow {
 
         imp.cancel_token.replace(Some(cancel_token));
 
-        // Download PKGBUILD with paru
-        let result = if let Ok(paru_path) = Paths::paru() {
-            TokioUtils::run(paru_path, &["-Gp", &self.pkg_name()], Some(cancel_token_clone))
-                .await
-        } else {
-            Err(io::Error::other("Failed to download PKGBUILD: paru not found"))
-        };
+        // Download PKGBUILD (or read from local clone)
+        let result = PkgbuildSource::fetch(&self.repository(), &self.pkg_base(), "PKGBUILD", cancel_token_clone)
+            .await;
 
         match result {
-            Ok((Some(0), pkgbuild)) => {
-                let buffer = self.buffer();
+            Ok(pkgbuild) => {
+                let mut files = HashMap::from([(String::from("PKGBUILD"), pkgbuild)]);
 
-                buffer.set_text(&pkgbuild);
+                if let Some(script) = self.install_script() {
+                    files.insert(String::from(INSTALLED_SCRIPT), script);
+                }
 
-                // Position cursor at start
-                buffer.place_cursor(&buffer.iter_at_offset(0));
+                imp.files.replace(files);
 
-                imp.stack.set_visible_child_name("text");
-                self.action_set_enabled("source.save", true);
+                // Reset file switcher to PKGBUILD
+                imp.file_model.splice(0, imp.file_model.n_items(), &["PKGBUILD"]);
+                imp.file_dropdown.set_selected(0);
+
+                self.display_file("PKGBUILD");
             },
-            Ok((Some(1), _)) => {
-                let error = "Failed to download PKGBUILD: paru error";
+            Err(error) => {
+                let error = format!("Failed to download PKGBUILD: {error}");
 
-                imp.error_status.set_description(Some(error));
+                imp.error_status.set_description(Some(&error));
                 imp.stack.set_visible_child_name("error");
+
+                // Installed script is still available from local database
+                if let Some(script) = self.install_script() {
+                    imp.files.replace(HashMap::from([(String::from(INSTALLED_SCRIPT), script)]));
+
+                    imp.file_model.splice(0, imp.file_model.n_items(), &["PKGBUILD", INSTALLED_SCRIPT]);
+                }
+            }
+        }
+
+        // Remove stored cancel token
+        imp.cancel_token.replace(None);
+
+        // Set loading property
+        self.set_loading(false);
+
+        // List other package source files
+        if imp.files.borrow().contains_key("PKGBUILD") {
+            let list_token = CancellationToken::new();
+
+            if let Some(old_token) = imp.list_token.replace(Some(list_token.clone())) {
+                old_token.cancel();
+            }
+
+            let mut files = PkgbuildSource::list_files(&self.repository(), &self.pkg_base(), list_token.clone())
+                .await;
+
+            // Discard file list if window was closed or refreshed
+            if list_token.is_cancelled() {
+                return
+            }
+
+            imp.list_token.replace(None);
+
+            if self.install_script().is_some() {
+                files.push(String::from(INSTALLED_SCRIPT));
+            }
+
+            let additions: Vec<&str> = files.iter()
+                .skip(1)
+                .map(String::as_str)
+                .collect();
+
+            imp.file_model.splice(1, imp.file_model.n_items().saturating_sub(1), &additions);
+
+            self.load_srcinfo().await;
+        }
+    }
+
+    //---------------------------------------
+    // Load srcinfo function
+    //---------------------------------------
+    #[allow(clippy::future_not_send)]
+    async fn load_srcinfo(&self) {
+        let imp = self.imp();
+
+        let Some(pkg) = imp.pkg.upgrade() else {
+            return
+        };
+
+        if pkg.has_srcinfo() {
+            return
+        }
+
+        let token = CancellationToken::new();
+
+        if let Some(old_token) = imp.srcinfo_token.replace(Some(token.clone())) {
+            old_token.cancel();
+        }
+
+        // Parse .SRCINFO into package metadata shown in info pane
+        let _ = pkg.srcinfo_future(token).await;
+    }
+
+    //---------------------------------------
+    // Selected file helper function
+    //---------------------------------------
+    fn selected_file(&self) -> Option<String> {
+        self.imp().file_dropdown.selected_item()
+            .and_downcast::<gtk::StringObject>()
+            .map(|object| object.string().to_string())
+    }
+
+    //---------------------------------------
+    // File language helper function
+    //---------------------------------------
+    fn file_language(file: &str) -> Option<sourceview5::Language> {
+        let manager = sourceview5::LanguageManager::default();
+
+        if file == "PKGBUILD" {
+            manager.language("pkgbuild")
+        } else if Self::is_script(file) {
+            manager.language("sh")
+        } else {
+            manager.guess_language(Some(file), None)
+        }
+    }
+
+    //---------------------------------------
+    // Is script helper function
+    //---------------------------------------
+    fn is_script(file: &str) -> bool {
+        file == "PKGBUILD" || file == INSTALLED_SCRIPT
+            || file.ends_with(".install") || file.ends_with(".sh")
+    }
+
+    //---------------------------------------
+    // Show file function
+    //---------------------------------------
+    #[allow(clippy::future_not_send)]
+    async fn show_file(&self, file: &str) {
+        let imp = self.imp();
+
+        self.set_diff(false);
+
+        if imp.files.borrow().contains_key(file) {
+            self.display_file(file);
+            return;
+        }
+
+        imp.stack.set_visible_child_name("loading");
+        self.action_set_enabled("source.save", false);
+
+        // Set loading property
+        self.set_loading(true);
+
+        // Create and store cancel token
+        let cancel_token = CancellationToken::new();
+
+        let cancel_token_clone = cancel_token.clone();
+
+        imp.cancel_token.replace(Some(cancel_token));
+
+        // Download file (or read from local clone)
+        let result = PkgbuildSource::fetch(&self.repository(), &self.pkg_base(), file, cancel_token_clone)
+            .await;
+
+        match result {
+            Ok(text) => {
+                imp.files.borrow_mut().insert(file.to_owned(), text);
+
+                self.display_file(file);
             },
             Err(error) => {
-                let error = format!("Failed to download PKGBUILD: {error}");
+                let error = format!("Failed to download {file}: {error}");
 
                 imp.error_status.set_description(Some(&error));
                 imp.stack.set_visible_child_name("error");
             }
-            _ => {}
         }
 
         // Remove stored cancel token
@@ -349,10 +606,207 @@ impl SourceWindow {
         self.set_loading(false);
     }
 
+    //---------------------------------------
+    // Display file function
+    //---------------------------------------
+    fn display_file(&self, file: &str) {
+        let imp = self.imp();
+
+        let files = imp.files.borrow();
+
+        let Some(text) = files.get(file) else {
+            return;
+        };
+
+        let buffer = self.buffer();
+
+        buffer.set_language(Self::file_language(file).as_ref());
+        buffer.set_text(text);
+
+        // Position cursor at start
+        buffer.place_cursor(&buffer.iter_at_offset(0));
+
+        // Annotate risky constructs in scripts
+        self.update_review(if Self::is_script(file) { text.as_str() } else { "" });
+
+        imp.stack.set_visible_child_name("text");
+        self.action_set_enabled("source.save", true);
+        self.action_set_enabled("source.diff", file == "PKGBUILD" && self.installed_version().is_some());
+    }
+
+    //---------------------------------------
+    // Show diff function
+    //---------------------------------------
+    #[allow(clippy::future_not_send)]
+    async fn show_diff(&self) {
+        let imp = self.imp();
+
+        if imp.diff_loaded.get() {
+            imp.stack.set_visible_child_name("diff");
+            return;
+        }
+
+        let Some(installed_version) = self.installed_version() else {
+            return;
+        };
+
+        imp.stack.set_visible_child_name("loading");
+
+        // Set loading property
+        self.set_loading(true);
+
+        // Create and store cancel token
+        let cancel_token = CancellationToken::new();
+
+        let cancel_token_clone = cancel_token.clone();
+
+        imp.cancel_token.replace(Some(cancel_token));
+
+        // Get PKGBUILD for installed version
+        let result = PkgbuildSource::fetch_version(&self.repository(), &self.pkg_base(), &installed_version, "PKGBUILD", cancel_token_clone)
+            .await;
+
+        match result {
+            Ok(old_pkgbuild) => {
+                let new_pkgbuild = imp.files.borrow().get("PKGBUILD").cloned()
+                    .unwrap_or_default();
+
+                self.populate_diff(&old_pkgbuild, &new_pkgbuild);
+
+                imp.diff_loaded.set(true);
+
+                if self.diff() {
+                    imp.stack.set_visible_child_name("diff");
+                }
+            },
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {
+                imp.stack.set_visible_child_name("text");
+            },
+            Err(error) => {
+                let error = format!("Failed to retrieve PKGBUILD for version {installed_version}: {error}");
+
+                imp.error_status.set_description(Some(&error));
+                imp.stack.set_visible_child_name("error");
+            }
+        }
+
+        // Remove stored cancel token
+        imp.cancel_token.replace(None);
+
+        // Set loading property
+        self.set_loading(false);
+    }
+
+    //---------------------------------------
+    // Populate diff function
+    //---------------------------------------
+    fn populate_diff(&self, old_pkgbuild: &str, new_pkgbuild: &str) {
+        let imp = self.imp();
+
+        let rows = DiffRow::side_by_side(old_pkgbuild, new_pkgbuild);
+
+        let mut old_text = String::new();
+        let mut new_text = String::new();
+
+        for row in &rows {
+            writeln!(old_text, "{}", row.old.unwrap_or_default()).unwrap();
+            writeln!(new_text, "{}", row.new.unwrap_or_default()).unwrap();
+        }
+
+        imp.old_buffer.set_text(&old_text);
+        imp.new_buffer.set_text(&new_text);
+
+        // Highlight changed lines
+        let tag_line = |buffer: &sourceview5::Buffer, line: usize, tag: &str| {
+            if let Some(start) = buffer.iter_at_line(line as i32) {
+                let mut end = start.clone();
+                end.forward_line();
+
+                buffer.apply_tag_by_name(tag, &start, &end);
+            }
+        };
+
+        for (line, row) in rows.iter().enumerate() {
+            let (old_tag, new_tag) = match row.kind {
+                DiffKind::Same => continue,
+                DiffKind::Removed => ("removed", "filler"),
+                DiffKind::Added => ("filler", "added"),
+                DiffKind::Changed => ("removed", "added")
+            };
+
+            tag_line(&imp.old_buffer, line, old_tag);
+            tag_line(&imp.new_buffer, line, new_tag);
+        }
+
+        let n_changes = rows.iter().filter(|row| row.kind != DiffKind::Same).count();
+
+        imp.old_label.set_label(&format!("Installed  \u{2022}  {}", self.installed_version().unwrap_or_default()));
+        imp.new_label.set_label(&format!("Current  \u{2022}  {}  \u{2022}  {n_changes} changed line{}",
+            self.update_version().unwrap_or_default(),
+            if n_changes == 1 { "" } else { "s" }
+        ));
+    }
+
+    //---------------------------------------
+    // Update review function
+    //---------------------------------------
+    fn update_review(&self, pkgbuild: &str) {
+        let imp = self.imp();
+
+        let buffer = self.buffer();
+
+        buffer.remove_source_marks(&buffer.start_iter(), &buffer.end_iter(), None);
+
+        imp.review_list.remove_all();
+
+        let findings = ReviewFinding::scan(pkgbuild);
+
+        for finding in &findings {
+            if let Some(iter) = buffer.iter_at_line(finding.line as i32) {
+                buffer.create_source_mark(None, finding.severity.category(), &iter);
+            }
+
+            let icon = gtk::Image::from_icon_name(finding.severity.icon_name());
+            icon.add_css_class(finding.severity.css_class());
+
+            let row = adw::ActionRow::builder()
+                .title(glib::markup_escape_text(&finding.message))
+                .subtitle(format!("Line {}", finding.line + 1))
+                .activatable(true)
+                .build();
+
+            row.add_prefix(&icon);
+
+            imp.review_list.append(&row);
+        }
+
+        // Update review button and label
+        let severity = findings.iter().map(|finding| finding.severity).max();
+
+        for other in ReviewSeverity::ALL {
+            imp.review_button.remove_css_class(other.css_class());
+        }
+
+        if let Some(severity) = severity.filter(|&severity| severity > ReviewSeverity::Info) {
+            imp.review_button.add_css_class(severity.css_class());
+        }
+
+        imp.review_label.set_label(&format!("Review  \u{2022}  {} finding{}",
+            findings.len(),
+            if findings.len() == 1 { "" } else { "s" }
+        ));
+
+        imp.review_stack.set_visible_child_name(if findings.is_empty() { "empty" } else { "findings" });
+
+        imp.review_findings.replace(findings);
+    }
+
     //---------------------------------------
     // Setup signals
     //---------------------------------------
     fn setup_signals(&self) {
+        let imp = self.imp();
+
         // System color scheme signal
         let display = gtk::prelude::WidgetExt::display(self);
         let style_manager = adw::StyleManager::for_display(&display);
@@ -369,10 +823,68 @@ impl SourceWindow {
             Self::set_font(style_manager, &display);
         });
 
+        // Review list row activated signal
+        imp.review_list.connect_row_activated(clone!(
+            #[weak(rename_to = window)] self,
+            move |_, row| {
+                let imp = window.imp();
+
+                let line = imp.review_findings.borrow().get(row.index() as usize)
+                    .map(|finding| finding.line);
+
+                if let Some(line) = line {
+                    let buffer = window.buffer();
+
+                    if let Some(mut iter) = buffer.iter_at_line(line as i32) {
+                        buffer.place_cursor(&iter);
+
+                        imp.source_view.scroll_to_iter(&mut iter, 0.0, true, 0.0, 0.3);
+                        imp.source_view.grab_focus();
+                    }
+                }
+            }
+        ));
+
+        // File dropdown selected item notify signal
+        imp.file_dropdown.connect_selected_item_notify(clone!(
+            #[weak(rename_to = window)] self,
+            move |_| {
+                // Ignore model changes while downloading
+                if window.loading() {
+                    return;
+                }
+
+                if let Some(file) = window.selected_file() {
+                    glib::spawn_future_local(clone!(
+                        #[weak] window,
+                        async move {
+                            window.show_file(&file).await;
+                        }
+                    ));
+                }
+            }
+        ));
+
+        // Diff property notify signal
+        self.connect_diff_notify(|window| {
+            if window.diff() {
+                glib::spawn_future_local(clone!(
+                    #[weak] window,
+                    async move {
+                        window.show_diff().await;
+                    }
+                ));
+            } else if window.imp().stack.visible_child_name().as_deref() == Some("diff") {
+                window.imp().stack.set_visible_child_name("text");
+            }
+        });
+
         // Loading property notify signal
         self.connect_loading_notify(|window| {
             let imp = window.imp();
 
+            imp.file_dropdown.set_sensitive(!window.loading());
+
             if window.loading() {
                 imp.refresh_button.set_icon_name("process-stop-symbolic");
                 imp.refresh_button.set_tooltip_text(Some("Cancel Download"));
@@ -387,9 +899,62 @@ impl SourceWindow {
     // Setup widgets
     //---------------------------------------
     fn setup_widgets(&self) {
+        let imp = self.imp();
+
         // Set source url action state
         self.action_set_enabled("source.url", self.pkgbuild_url().is_some());
 
+        // Bind review property to split view
+        self.bind_property("review", &imp.split_view.get(), "show-sidebar")
+            .sync_create()
+            .bidirectional()
+            .build();
+
+        // Show diff button if update pending
+        imp.diff_button.set_visible(self.installed_version().is_some());
+
+        // Synchronize diff view scrolling
+        imp.new_scroll.set_vadjustment(Some(&imp.old_scroll.vadjustment()));
+        imp.new_scroll.set_hadjustment(Some(&imp.old_scroll.hadjustment()));
+
+        // Create diff highlight tags
+        for diff_buffer in [&imp.old_buffer.get(), &imp.new_buffer.get()] {
+            diff_buffer.set_language(sourceview5::LanguageManager::default().language("pkgbuild").as_ref());
+
+            diff_buffer.create_tag(Some("removed"), &[("paragraph-background", &"rgba(224, 27, 36, 0.2)")]);
+            diff_buffer.create_tag(Some("added"), &[("paragraph-background", &"rgba(46, 194, 126, 0.2)")]);
+            diff_buffer.create_tag(Some("filler"), &[("paragraph-background", &"rgba(128, 128, 128, 0.1)")]);
+        }
+
+        // Show review panel by default for AUR packages
+        self.set_review(self.repository() == "aur");
+
+        // Set review mark attributes
+        for severity in ReviewSeverity::ALL {
+            let attributes = sourceview5::MarkAttributes::builder()
+                .icon_name(severity.icon_name())
+                .background(&gdk::RGBA::parse(severity.color()).expect("Failed to parse RGBA"))
+                .build();
+
+            attributes.connect_query_tooltip_text(clone!(
+                #[weak(rename_to = window)] self,
+                #[upgrade_or_default]
+                move |_, mark| {
+                    let Some(line) = mark.buffer().map(|buffer| buffer.iter_at_mark(mark).line()) else {
+                        return String::new();
+                    };
+
+                    window.imp().review_findings.borrow().iter()
+                        .filter(|finding| finding.line as i32 == line && mark.category().as_str() == finding.severity.category())
+                        .map(|finding| finding.message.as_str())
+                        .collect::<Vec<&str>>()
+                        .join("\n")
+                }
+            ));
+
+            imp.source_view.set_mark_attributes(severity.category(), &attributes, severity as i32);
+        }
+
         // Set syntax highlighting language
         let buffer = self.buffer();
 
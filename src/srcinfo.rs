@@ -0,0 +1,341 @@
+//------------------------------------------------------------------------------
+// CONST variables
+//------------------------------------------------------------------------------
+const CHECKSUM_KINDS: [&str; 8] = ["ck", "md5", "sha1", "sha224", "sha256", "sha384", "sha512", "b2"];
+
+//------------------------------------------------------------------------------
+// STRUCT: ArchValue
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchValue {
+    pub arch: Option<String>,
+    pub value: String
+}
+
+impl ArchValue {
+    fn to_display_string(&self) -> String {
+        match &self.arch {
+            Some(arch) => format!("{} [{arch}]", self.value),
+            None => self.value.clone()
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: SrcInfoSource
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct SrcInfoSource {
+    pub arch: Option<String>,
+    pub source: String,
+    pub checksums: Vec<(String, String)>
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: SrcInfoPkg
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Clone)]
+pub struct SrcInfoPkg {
+    pub name: String,
+    pub arch: Vec<String>,
+    pub depends: Vec<ArchValue>,
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: SrcInfo
+//------------------------------------------------------------------------------
+#[derive(Default, Debug, Clone)]
+pub struct SrcInfo {
+    pub base: String,
+    pub pkgver: String,
+    pub pkgrel: String,
+    pub epoch: Option<String>,
+    pub arch: Vec<String>,
+    pub depends: Vec<ArchValue>,
+    pub checkdepends: Vec<ArchValue>,
+    pub sources: Vec<SrcInfoSource>,
+    pub validpgpkeys: Vec<String>,
+    pub install: Vec<String>,
+    pub changelog: Option<String>,
+    pub packages: Vec<SrcInfoPkg>,
+}
+
+impl SrcInfo {
+    //---------------------------------------
+    // Split key helper function
+    //---------------------------------------
+    fn split_key<'a>(key: &'a str, prefix: &str) -> Option<Option<&'a str>> {
+        if key == prefix {
+            Some(None)
+        } else {
+            key.strip_prefix(prefix)
+                .and_then(|rest| rest.strip_prefix('_'))
+                .map(Some)
+        }
+    }
+
+    //---------------------------------------
+    // Parse function
+    //---------------------------------------
+    pub fn parse(srcinfo: &str) -> Option<Self> {
+        let mut info = Self::default();
+
+        let mut checksums: Vec<(Option<String>, String, String)> = vec![];
+
+        for line in srcinfo.lines() {
+            let Some((key, value)) = line.trim().split_once(" = ") else {
+                continue;
+            };
+
+            let value = value.to_owned();
+
+            if key == "pkgbase" {
+                info.base = value;
+                continue;
+            }
+
+            if key == "pkgname" {
+                info.packages.push(SrcInfoPkg { name: value, ..SrcInfoPkg::default() });
+                continue;
+            }
+
+            if key == "install" {
+                if !info.install.contains(&value) {
+                    info.install.push(value);
+                }
+
+                continue;
+            }
+
+            if key == "changelog" {
+                info.changelog = Some(value);
+                continue;
+            }
+
+            // Package section overrides
+            if let Some(pkg) = info.packages.last_mut() {
+                if key == "arch" {
+                    pkg.arch.push(value);
+                } else if let Some(arch) = Self::split_key(key, "depends") {
+                    pkg.depends.push(ArchValue { arch: arch.map(ToOwned::to_owned), value });
+                }
+
+                continue;
+            }
+
+            // Package base section
+            match key {
+                "pkgver" => info.pkgver = value,
+                "pkgrel" => info.pkgrel = value,
+                "epoch" => info.epoch = Some(value),
+                "arch" => info.arch.push(value),
+                "validpgpkeys" => info.validpgpkeys.push(value),
+                _ => {
+                    if let Some(arch) = Self::split_key(key, "depends") {
+                        info.depends.push(ArchValue { arch: arch.map(ToOwned::to_owned), value });
+                    } else if let Some(arch) = Self::split_key(key, "checkdepends") {
+                        info.checkdepends.push(ArchValue { arch: arch.map(ToOwned::to_owned), value });
+                    } else if let Some(arch) = Self::split_key(key, "source") {
+                        info.sources.push(SrcInfoSource { arch: arch.map(ToOwned::to_owned), source: value, checksums: vec![] });
+                    } else if let Some((kind, arch)) = CHECKSUM_KINDS.iter()
+                        .find_map(|kind| Self::split_key(key, &format!("{kind}sums")).map(|arch| (kind, arch))) {
+                            checksums.push((arch.map(ToOwned::to_owned), (*kind).to_owned(), value));
+                        }
+                }
+            }
+        }
+
+        if info.base.is_empty() || info.pkgver.is_empty() {
+            return None;
+        }
+
+        // Match checksums to sources by position within each architecture
+        for (arch, kind, checksum) in checksums {
+            let position = info.sources.iter()
+                .filter(|source| source.arch == arch)
+                .position(|source| !source.checksums.iter().any(|(k, _)| *k == kind));
+
+            if let Some(source) = position.and_then(|position| {
+                info.sources.iter_mut().filter(|source| source.arch == arch).nth(position)
+            }) {
+                source.checksums.push((kind, checksum));
+            }
+        }
+
+        Some(info)
+    }
+
+    //---------------------------------------
+    // Version function
+    //---------------------------------------
+    pub fn version(&self) -> String {
+        match &self.epoch {
+            Some(epoch) => format!("{epoch}:{}-{}", self.pkgver, self.pkgrel),
+            None => format!("{}-{}", self.pkgver, self.pkgrel)
+        }
+    }
+
+    //---------------------------------------
+    // Split packages function
+    //---------------------------------------
+    pub fn split_packages(&self) -> Vec<String> {
+        self.packages.iter()
+            .map(|pkg| pkg.name.clone())
+            .collect()
+    }
+
+    //---------------------------------------
+    // Package architecture function
+    //---------------------------------------
+    pub fn package_arch(&self, name: &str) -> Vec<String> {
+        self.packages.iter()
+            .find(|pkg| pkg.name == name && !pkg.arch.is_empty())
+            .map_or_else(|| self.arch.clone(), |pkg| pkg.arch.clone())
+    }
+
+    //---------------------------------------
+    // Architecture specific dependencies function
+    //---------------------------------------
+    pub fn arch_depends(&self, name: &str) -> Vec<String> {
+        let depends = self.packages.iter()
+            .find(|pkg| pkg.name == name && !pkg.depends.is_empty())
+            .map_or(&self.depends, |pkg| &pkg.depends);
+
+        depends.iter()
+            .filter(|dep| dep.arch.is_some())
+            .map(ArchValue::to_display_string)
+            .collect()
+    }
+
+    //---------------------------------------
+    // Check dependencies function
+    //---------------------------------------
+    pub fn checkdepends(&self) -> Vec<String> {
+        self.checkdepends.iter()
+            .map(ArchValue::to_display_string)
+            .collect()
+    }
+
+    //---------------------------------------
+    // Local files function
+    //---------------------------------------
+    pub fn local_files(&self) -> Vec<String> {
+        let sources = self.sources.iter()
+            .map(|source| source.source.rsplit_once("::").map_or(source.source.as_str(), |(_, file)| file))
+            .filter(|file| !file.contains("://"));
+
+        let mut files: Vec<String> = vec![];
+
+        for file in self.install.iter().map(String::as_str).chain(self.changelog.as_deref()).chain(sources) {
+            if !files.iter().any(|f| f == file) {
+                files.push(file.to_owned());
+            }
+        }
+
+        files
+    }
+
+    //---------------------------------------
+    // Sources with checksums function
+    //---------------------------------------
+    pub fn sources(&self) -> Vec<String> {
+        self.sources.iter()
+            .map(|source| {
+                let mut line = ArchValue { arch: source.arch.clone(), value: source.source.clone() }
+                    .to_display_string();
+
+                if let Some((kind, checksum)) = source.checksums.iter()
+                    .max_by_key(|(kind, _)| CHECKSUM_KINDS.iter().position(|k| k == kind)) {
+                        line.push_str(&format!("  ({kind}: {checksum})"));
+                    }
+
+                line
+            })
+            .collect()
+    }
+}
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRCINFO: &str = "\
+pkgbase = example
+\tpkgdesc = Example split package
+\tpkgver = 1.2.3
+\tpkgrel = 2
+\tepoch = 1
+\tarch = x86_64
+\tarch = aarch64
+\tcheckdepends = python-pytest
+\tdepends = glibc
+\tdepends_x86_64 = lib32-glibc
+\tinstall = example.install
+\tchangelog = example.changelog
+\tsource = https://example.com/example-1.2.3.tar.gz
+\tsource = example.patch
+\tsource_aarch64 = renamed.bin::https://example.com/arm.bin
+\tvalidpgpkeys = 0123456789ABCDEF0123456789ABCDEF01234567
+\tmd5sums = aaaa
+\tmd5sums = bbbb
+\tsha256sums = cccc
+\tsha256sums = dddd
+\tsha256sums_aarch64 = eeee
+
+pkgname = example
+\tdepends_aarch64 = libarm
+
+pkgname = example-any
+\tarch = any
+";
+
+    #[test]
+    fn parse_package_base() {
+        let info = SrcInfo::parse(SRCINFO).expect("Failed to parse .SRCINFO");
+
+        assert_eq!(info.base, "example");
+        assert_eq!(info.version(), "1:1.2.3-2");
+        assert_eq!(info.validpgpkeys, ["0123456789ABCDEF0123456789ABCDEF01234567"]);
+        assert_eq!(info.checkdepends(), ["python-pytest"]);
+        assert_eq!(info.split_packages(), ["example", "example-any"]);
+    }
+
+    #[test]
+    fn parse_package_overrides() {
+        let info = SrcInfo::parse(SRCINFO).expect("Failed to parse .SRCINFO");
+
+        assert_eq!(info.package_arch("example"), ["x86_64", "aarch64"]);
+        assert_eq!(info.package_arch("example-any"), ["any"]);
+
+        assert_eq!(info.arch_depends("example"), ["libarm [aarch64]"]);
+        assert_eq!(info.arch_depends("example-any"), ["lib32-glibc [x86_64]"]);
+    }
+
+    #[test]
+    fn parse_sources_and_checksums() {
+        let info = SrcInfo::parse(SRCINFO).expect("Failed to parse .SRCINFO");
+
+        assert_eq!(info.sources(), [
+            "https://example.com/example-1.2.3.tar.gz  (sha256: cccc)",
+            "example.patch  (sha256: dddd)",
+            "renamed.bin::https://example.com/arm.bin [aarch64]  (sha256: eeee)",
+        ]);
+
+        assert_eq!(info.local_files(), ["example.install", "example.changelog", "example.patch"]);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(SrcInfo::parse("").is_none());
+        assert!(SrcInfo::parse("pkgname = example\n\tpkgver = 1.0").is_none());
+        assert!(SrcInfo::parse("not a srcinfo file").is_none());
+
+        let info = SrcInfo::parse("pkgbase = example\npkgver = 1.0\npkgrel = 1").expect("Failed to parse .SRCINFO");
+
+        assert_eq!(info.version(), "1.0-1");
+        assert!(info.sources().is_empty());
+    }
+}
@@ -1,5 +1,9 @@
 use std::cell::Cell;
 use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+use std::time::UNIX_EPOCH;
 
 use gtk::{glib, gio, gdk};
 use adw::subclass::prelude::*;
@@ -10,12 +14,21 @@ use glib::clone;
 use itertools::Itertools;
 use size::Size;
 use heck::ToTitleCase;
+use regex::Regex;
 
 use crate::{
+    pkg_data::PkgFlags,
     pkg_object::PkgObject,
-    stats_object::StatsObject
+    stats_object::StatsObject,
+    sync_db_object::SyncDbObject,
+    utils::Pacman
 };
 
+//------------------------------------------------------------------------------
+// CONST variables
+//------------------------------------------------------------------------------
+const STALE_DB_DAYS: i64 = 7;
+
 //------------------------------------------------------------------------------
 // MODULE: StatsWindow
 //------------------------------------------------------------------------------
@@ -36,6 +49,11 @@ mod imp {
         #[template_child]
         pub(super) selection: TemplateChild<gtk::NoSelection>,
 
+        #[template_child]
+        pub(super) risk_banner: TemplateChild<adw::Banner>,
+        #[template_child]
+        pub(super) db_model: TemplateChild<gio::ListStore>,
+
         #[property(get, set)]
         is_loaded: Cell<bool>,
     }
@@ -51,6 +69,7 @@ mod imp {
 
         fn class_init(klass: &mut Self::Class) {
             StatsObject::ensure_type();
+            SyncDbObject::ensure_type();
 
             klass.bind_template();
 
@@ -106,6 +125,22 @@ mod imp {
                         .unwrap();
                     }
 
+                output.push_str("\n## Sync Databases\n|Database|Updated|Size|Packages|Files Database|Status|\n|---|---|---|---|---|---|\n");
+
+                for db in window.imp().db_model.iter::<SyncDbObject>()
+                    .flatten() {
+                        writeln!(output,
+                            "|{repository}|{updated}|{size}|{packages}|{files_db}|{status}|",
+                            repository=db.repository(),
+                            updated=db.updated(),
+                            size=db.size(),
+                            packages=db.packages(),
+                            files_db=db.files_db(),
+                            status=if db.warnings().is_empty() { db.status() } else { db.warnings().replace('\n', "; ") }
+                        )
+                        .unwrap();
+                    }
+
                 window.clipboard().set_text(&output);
             });
         }
@@ -221,10 +256,141 @@ impl StatsWindow {
                 ));
 
                 imp.model.splice(0, imp.model.n_items(), &stats_items);
+
+                // Build sync database list
+                let db_items = Self::sync_db_items(&pkg_model);
+
+                imp.risk_banner.set_revealed(db_items.iter().any(|(_, is_risk)| *is_risk));
+
+                let db_items: Vec<SyncDbObject> = db_items.into_iter()
+                    .map(|(item, _)| item)
+                    .collect();
+
+                imp.db_model.splice(0, imp.db_model.n_items(), &db_items);
             }
         ));
     }
 
+    //---------------------------------------
+    // Sync database helper functions
+    //---------------------------------------
+    fn modified_time(path: &Path) -> Option<(i64, u64)> {
+        let metadata = fs::metadata(path).ok()?;
+
+        let modified = metadata.modified().ok()?
+            .duration_since(UNIX_EPOCH).ok()?
+            .as_secs();
+
+        Some((modified as i64, metadata.len()))
+    }
+
+    fn age_string(age: i64) -> String {
+        let age = age.max(0);
+
+        match age {
+            0..3600 => format!("{} min ago", age / 60),
+            3600..86400 => format!("{} h ago", age / 3600),
+            _ => format!("{} d ago", age / 86400)
+        }
+    }
+
+    fn last_full_upgrade() -> Option<i64> {
+        static EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"\[(.+?)\] \[PACMAN\] starting full system upgrade")
+                .expect("Failed to compile Regex")
+        });
+
+        let pacman_log = Pacman::log().read().unwrap();
+
+        pacman_log.as_ref()?
+            .lines()
+            .rev()
+            .find_map(|line| EXPR.captures(line))
+            .and_then(|caps| glib::DateTime::from_iso8601(&caps[1], None).ok())
+            .map(|datetime| datetime.to_unix())
+    }
+
+    fn sync_db_items(pkg_model: &gio::ListStore) -> Vec<(SyncDbObject, bool)> {
+        let config = Pacman::config();
+
+        let sync_dir = Path::new(&config.db_path).join("sync");
+
+        let now = glib::DateTime::now_utc().map_or(0, |now| now.to_unix());
+        let last_upgrade = Self::last_full_upgrade();
+
+        let pkgs: Vec<PkgObject> = pkg_model.iter::<PkgObject>()
+            .flatten()
+            .collect();
+
+        config.repos.iter()
+            .map(|repo| {
+                let mut warnings: Vec<String> = vec![];
+                let mut is_risk = false;
+
+                let repo_pkgs: Vec<&PkgObject> = pkgs.iter()
+                    .filter(|pkg| pkg.repository() == repo.name)
+                    .collect();
+
+                // Get sync database file info
+                let db_info = Self::modified_time(&sync_dir.join(format!("{}.db", repo.name)));
+                let files_info = Self::modified_time(&sync_dir.join(format!("{}.files", repo.name)));
+
+                let (updated, size) = if let Some((db_time, db_size)) = db_info {
+                    let age = now - db_time;
+
+                    if age > STALE_DB_DAYS * 86400 {
+                        warnings.push(format!("Database not updated in {} days", age / 86400));
+                    }
+
+                    if last_upgrade.is_some_and(|upgrade_time| db_time > upgrade_time) {
+                        warnings.push(String::from("Database updated after last full system upgrade"));
+                        is_risk = true;
+                    }
+
+                    (Self::age_string(age), Size::from_bytes(db_size).to_string())
+                } else {
+                    warnings.push(String::from("Database file not found"));
+
+                    (String::from("Never"), String::from("\u{2014}"))
+                };
+
+                // Get files database status
+                let files_db = match (db_info, files_info) {
+                    (_, None) => "Missing",
+                    (Some((db_time, _)), Some((files_time, _))) if files_time < db_time => {
+                        warnings.push(String::from("Files database older than sync database"));
+
+                        "Outdated"
+                    },
+                    _ => "Current"
+                };
+
+                // Check for installed packages newer than sync database
+                let n_newer = repo_pkgs.iter()
+                    .filter(|pkg| pkg.flags().contains(PkgFlags::NEWER))
+                    .count();
+
+                if n_newer > 0 {
+                    warnings.push(format!("{n_newer} installed package{} newer than sync database",
+                        if n_newer == 1 { "" } else { "s" }
+                    ));
+                    is_risk = true;
+                }
+
+                let item = SyncDbObject::new(
+                    &repo.name,
+                    &updated,
+                    &size,
+                    &repo_pkgs.len().to_string(),
+                    files_db,
+                    &warnings
+                );
+
+                (item, is_risk)
+            })
+            .collect()
+    }
+
     //---------------------------------------
     // Show window
     //---------------------------------------
@@ -0,0 +1,80 @@
+use std::cell::RefCell;
+
+use gtk::glib;
+use gtk::subclass::prelude::*;
+use gtk::prelude::ObjectExt;
+
+//------------------------------------------------------------------------------
+// MODULE: SyncDbObject
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, glib::Properties)]
+    #[properties(wrapper_type = super::SyncDbObject)]
+    pub struct SyncDbObject {
+        #[property(get, set, construct_only)]
+        repository: RefCell<String>,
+        #[property(get, set, construct_only)]
+        updated: RefCell<String>,
+        #[property(get, set, construct_only)]
+        size: RefCell<String>,
+        #[property(get, set, construct_only)]
+        packages: RefCell<String>,
+        #[property(get, set, construct_only)]
+        files_db: RefCell<String>,
+        #[property(get, set, construct_only)]
+        status: RefCell<String>,
+        #[property(get, set, construct_only)]
+        status_css_classes: RefCell<Vec<String>>,
+        #[property(get, set, construct_only)]
+        warnings: RefCell<String>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for SyncDbObject {
+        const NAME: &'static str = "SyncDbObject";
+        type Type = super::SyncDbObject;
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for SyncDbObject {}
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: SyncDbObject
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct SyncDbObject(ObjectSubclass<imp::SyncDbObject>);
+}
+
+impl SyncDbObject {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    pub fn new(repository: &str, updated: &str, size: &str, packages: &str, files_db: &str, warnings: &[String]) -> Self {
+        let (status, css_class) = match warnings.len() {
+            0 => (String::from("Current"), "success"),
+            1 => (String::from("1 warning"), "warning"),
+            n => (format!("{n} warnings"), "warning")
+        };
+
+        // Build SyncDbObject
+        glib::Object::builder()
+            .property("repository", repository)
+            .property("updated", updated)
+            .property("size", size)
+            .property("packages", packages)
+            .property("files-db", files_db)
+            .property("status", status)
+            .property("status-css-classes", ["tag", css_class].as_slice())
+            .property("warnings", warnings.join("\n"))
+            .build()
+    }
+}
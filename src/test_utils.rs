@@ -0,0 +1,214 @@
+use std::env;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::Duration;
+
+//------------------------------------------------------------------------------
+// STRUCT: TestRequest
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct TestRequest {
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+}
+
+impl TestRequest {
+    //---------------------------------------
+    // Header function
+    //---------------------------------------
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: TestResponse
+//------------------------------------------------------------------------------
+#[derive(Debug, Clone)]
+pub struct TestResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+    pub chunk_delay: Option<Duration>,
+}
+
+impl TestResponse {
+    //---------------------------------------
+    // Constructors
+    //---------------------------------------
+    pub fn new(status: u16, body: impl Into<Vec<u8>>) -> Self {
+        Self {
+            status,
+            headers: vec![],
+            body: body.into(),
+            chunk_delay: None,
+        }
+    }
+
+    pub fn ok(body: impl Into<Vec<u8>>) -> Self {
+        Self::new(200, body)
+    }
+
+    pub fn not_found() -> Self {
+        Self::new(404, "Not Found")
+    }
+
+    //---------------------------------------
+    // Builder functions
+    //---------------------------------------
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    pub fn slow(mut self, chunk_delay: Duration) -> Self {
+        self.chunk_delay = Some(chunk_delay);
+        self
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: TestServer
+//------------------------------------------------------------------------------
+// Minimal HTTP/1.1 server on a random local port for network tests
+pub struct TestServer {
+    addr: SocketAddr,
+}
+
+impl TestServer {
+    //---------------------------------------
+    // Start function
+    //---------------------------------------
+    pub fn start<F>(handler: F) -> Self
+    where F: Fn(&TestRequest) -> TestResponse + Send + Sync + 'static {
+        let listener = TcpListener::bind("127.0.0.1:0").expect("Failed to bind test server");
+        let addr = listener.local_addr().expect("Failed to get test server address");
+
+        let handler = Arc::new(handler);
+
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let handler = Arc::clone(&handler);
+
+                thread::spawn(move || {
+                    if let Some(request) = Self::read_request(&stream) {
+                        Self::write_response(stream, &handler(&request));
+                    }
+                });
+            }
+        });
+
+        Self { addr }
+    }
+
+    //---------------------------------------
+    // Public functions
+    //---------------------------------------
+    pub fn url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    //---------------------------------------
+    // Request/response helper functions
+    //---------------------------------------
+    fn read_request(stream: &TcpStream) -> Option<TestRequest> {
+        let mut reader = BufReader::new(stream);
+
+        let mut request_line = String::new();
+        reader.read_line(&mut request_line).ok()?;
+
+        let path = request_line.split_whitespace().nth(1)?.to_owned();
+
+        let mut headers = vec![];
+
+        loop {
+            let mut line = String::new();
+
+            if reader.read_line(&mut line).ok()? == 0 || line.trim().is_empty() {
+                break;
+            }
+
+            if let Some((name, value)) = line.split_once(':') {
+                headers.push((name.trim().to_owned(), value.trim().to_owned()));
+            }
+        }
+
+        Some(TestRequest { path, headers })
+    }
+
+    fn write_response(mut stream: TcpStream, response: &TestResponse) {
+        let mut head = format!("HTTP/1.1 {} Test\r\nContent-Length: {}\r\nConnection: close\r\n",
+            response.status,
+            response.body.len()
+        );
+
+        for (name, value) in &response.headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+
+        head.push_str("\r\n");
+
+        if stream.write_all(head.as_bytes()).is_err() {
+            return;
+        }
+
+        if let Some(delay) = response.chunk_delay {
+            for chunk in response.body.chunks(1024) {
+                if stream.write_all(chunk).and_then(|()| stream.flush()).is_err() {
+                    return;
+                }
+
+                thread::sleep(delay);
+            }
+        } else {
+            let _ = stream.write_all(&response.body);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: TestDir
+//------------------------------------------------------------------------------
+// Unique temporary directory removed on drop
+pub struct TestDir {
+    path: PathBuf,
+}
+
+impl TestDir {
+    //---------------------------------------
+    // New function
+    //---------------------------------------
+    pub fn new(name: &str) -> Self {
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let path = env::temp_dir().join(format!("pacview-{name}-{}-{}",
+            process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        fs::create_dir_all(&path).expect("Failed to create test directory");
+
+        Self { path }
+    }
+
+    //---------------------------------------
+    // Public functions
+    //---------------------------------------
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for TestDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
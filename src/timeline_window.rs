@@ -0,0 +1,355 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{BTreeMap, HashSet};
+use std::fmt::Write as _;
+
+use gtk::{glib, gio, gdk};
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use glib::clone;
+use gdk::{Key, ModifierType};
+
+use crate::{
+    pkg_object::PkgObject,
+    log_object::{LogObject, PkgEvent},
+    utils::Pacman
+};
+
+//------------------------------------------------------------------------------
+// MODULE: TimelineWindow
+//------------------------------------------------------------------------------
+mod imp {
+    use super::*;
+
+    //---------------------------------------
+    // Private structure
+    //---------------------------------------
+    #[derive(Default, gtk::CompositeTemplate, glib::Properties)]
+    #[properties(wrapper_type = super::TimelineWindow)]
+    #[template(resource = "/com/github/PacView/ui/timeline_window.ui")]
+    pub struct TimelineWindow {
+        #[template_child]
+        pub(super) calendar: TemplateChild<gtk::Calendar>,
+
+        #[template_child]
+        pub(super) stack: TemplateChild<gtk::Stack>,
+        #[template_child]
+        pub(super) view: TemplateChild<gtk::ColumnView>,
+        #[template_child]
+        pub(super) model: TemplateChild<gio::ListStore>,
+        #[template_child]
+        pub(super) selection: TemplateChild<gtk::NoSelection>,
+
+        #[template_child]
+        pub(super) footer_label: TemplateChild<gtk::Label>,
+
+        #[property(get, set)]
+        is_loaded: Cell<bool>,
+        #[property(get, set)]
+        since: Cell<bool>,
+
+        pub(super) events: RefCell<BTreeMap<String, Vec<PkgEvent>>>,
+    }
+
+    //---------------------------------------
+    // Subclass
+    //---------------------------------------
+    #[glib::object_subclass]
+    impl ObjectSubclass for TimelineWindow {
+        const NAME: &'static str = "TimelineWindow";
+        type Type = super::TimelineWindow;
+        type ParentType = adw::Window;
+
+        fn class_init(klass: &mut Self::Class) {
+            LogObject::ensure_type();
+
+            klass.bind_template();
+
+            // Install actions
+            Self::install_actions(klass);
+
+            // Add key bindings
+            Self::bind_shortcuts(klass);
+        }
+
+        fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
+            obj.init_template();
+        }
+    }
+
+    #[glib::derived_properties]
+    impl ObjectImpl for TimelineWindow {
+        //---------------------------------------
+        // Constructor
+        //---------------------------------------
+        fn constructed(&self) {
+            self.parent_constructed();
+
+            let obj = self.obj();
+
+            obj.setup_signals();
+        }
+    }
+
+    impl WidgetImpl for TimelineWindow {}
+    impl WindowImpl for TimelineWindow {}
+    impl AdwWindowImpl for TimelineWindow {}
+
+    impl TimelineWindow {
+        //---------------------------------------
+        // Install actions
+        //---------------------------------------
+        fn install_actions(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Since property action
+            klass.install_property_action("timeline.since", "since");
+
+            // Today action
+            klass.install_action("timeline.today", None, |window, _, _| {
+                if let Ok(now) = glib::DateTime::now_local() {
+                    window.imp().calendar.select_day(&now);
+                }
+            });
+
+            // Copy action
+            klass.install_action("timeline.copy", None, |window, _, _| {
+                let mut output = String::from("## Package Changes\n|Date|Time|Action|Package|\n|---|---|---|---|\n");
+
+                for event in window.imp().selection.iter::<glib::Object>()
+                    .flatten()
+                    .filter_map(|item| item.downcast::<LogObject>().ok()) {
+                        writeln!(output, "|{date}|{time}|{action}|{package}|",
+                            date=event.date(),
+                            time=event.time(),
+                            action=event.category(),
+                            package=event.message()
+                        )
+                        .unwrap();
+                    }
+
+                window.clipboard().set_text(&output);
+            });
+        }
+
+        //---------------------------------------
+        // Bind shortcuts
+        //---------------------------------------
+        fn bind_shortcuts(klass: &mut <Self as ObjectSubclass>::Class) {
+            // Close window binding
+            klass.add_binding_action(Key::Escape, ModifierType::NO_MODIFIER_MASK, "window.close");
+
+            // Since key binding
+            klass.add_binding_action(Key::S, ModifierType::CONTROL_MASK, "timeline.since");
+
+            // Today key binding
+            klass.add_binding_action(Key::T, ModifierType::CONTROL_MASK, "timeline.today");
+
+            // Copy key binding
+            klass.add_binding_action(Key::C, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "timeline.copy");
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// IMPLEMENTATION: TimelineWindow
+//------------------------------------------------------------------------------
+glib::wrapper! {
+    pub struct TimelineWindow(ObjectSubclass<imp::TimelineWindow>)
+        @extends adw::Window, gtk::Window, gtk::Widget,
+        @implements gtk::Accessible, gtk::Buildable, gtk::ConstraintTarget, gtk::Native, gtk::Root, gtk::ShortcutManager;
+}
+
+impl TimelineWindow {
+    //---------------------------------------
+    // Setup signals
+    //---------------------------------------
+    fn setup_signals(&self) {
+        let imp = self.imp();
+
+        // Calendar day selected signal
+        imp.calendar.connect_day_selected(clone!(
+            #[weak(rename_to = window)] self,
+            move |_| {
+                window.update_marks();
+                window.update_view();
+            }
+        ));
+
+        // Calendar month/year changed signals
+        let update_marks = clone!(
+            #[weak(rename_to = window)] self,
+            move |_: &gtk::Calendar| {
+                window.update_marks();
+                window.update_view();
+            }
+        );
+
+        imp.calendar.connect_next_month(update_marks.clone());
+        imp.calendar.connect_prev_month(update_marks.clone());
+        imp.calendar.connect_next_year(update_marks.clone());
+        imp.calendar.connect_prev_year(update_marks);
+
+        // Since property notify signal
+        self.connect_since_notify(|window| {
+            window.update_view();
+        });
+
+        // Model items changed signal
+        imp.model.connect_items_changed(clone!(
+            #[weak] imp,
+            move |model, _, _, _| {
+                imp.stack.set_visible_child_name(if model.n_items() == 0 { "empty" } else { "view" });
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Selected date helper function
+    //---------------------------------------
+    fn selected_date(&self) -> String {
+        self.imp().calendar.date().format("%Y-%m-%d")
+            .map(|date| date.to_string())
+            .unwrap_or_default()
+    }
+
+    //---------------------------------------
+    // Update calendar marks
+    //---------------------------------------
+    fn update_marks(&self) {
+        let imp = self.imp();
+
+        let date = imp.calendar.date();
+        let prefix = format!("{:04}-{:02}-", date.year(), date.month());
+
+        imp.calendar.clear_marks();
+
+        for key in imp.events.borrow().keys().filter(|key| key.starts_with(&prefix)) {
+            if let Ok(day) = key[prefix.len()..].parse::<u32>() {
+                imp.calendar.mark_day(day);
+            }
+        }
+    }
+
+    //---------------------------------------
+    // Update view
+    //---------------------------------------
+    fn update_view(&self) {
+        let imp = self.imp();
+
+        let date = self.selected_date();
+        let since = self.since();
+
+        let events = imp.events.borrow();
+
+        let items: Vec<LogObject> = if since {
+            events.range(date.clone()..)
+                .flat_map(|(_, events)| events.iter().map(LogObject::from))
+                .collect()
+        } else {
+            events.get(&date)
+                .map(|events| events.iter().map(LogObject::from).collect())
+                .unwrap_or_default()
+        };
+
+        let n_packages = items.iter()
+            .filter_map(|item| item.message().split_once(' ').map(|(name, _)| name.to_owned()))
+            .collect::<HashSet<String>>()
+            .len();
+
+        imp.footer_label.set_label(&format!("{} change{} to {n_packages} package{} {} {date}",
+            items.len(),
+            if items.len() == 1 { "" } else { "s" },
+            if n_packages == 1 { "" } else { "s" },
+            if since { "since" } else { "on" }
+        ));
+
+        imp.model.splice(0, imp.model.n_items(), &items);
+    }
+
+    //---------------------------------------
+    // Populate window
+    //---------------------------------------
+    fn populate(&self, pkg_model: &gio::ListStore) {
+        // Get install dates of installed packages (for events missing from log)
+        let install_events: Vec<PkgEvent> = pkg_model.iter::<PkgObject>()
+            .flatten()
+            .filter_map(|pkg| {
+                let datetime = pkg.install_date()
+                    .and_then(|date| glib::DateTime::from_unix_local(date).ok())?;
+
+                Some(PkgEvent {
+                    date: datetime.format("%Y-%m-%d").ok()?.to_string(),
+                    time: datetime.format("%H:%M:%S").ok()?.to_string(),
+                    action: String::from("installed"),
+                    name: pkg.name(),
+                    version: pkg.version()
+                })
+            })
+            .collect();
+
+        glib::spawn_future_local(clone!(
+            #[weak(rename_to = window)] self,
+            async move {
+                let events = gio::spawn_blocking(move || {
+                    let mut events: Vec<PkgEvent> = Pacman::log().read().unwrap().as_ref()
+                        .map(|log| PkgEvent::from_log(log))
+                        .unwrap_or_default();
+
+                    let logged: HashSet<(String, String)> = events.iter()
+                        .filter(|event| event.action != "removed")
+                        .map(|event| (event.date.clone(), event.name.clone()))
+                        .collect();
+
+                    events.extend(install_events.into_iter()
+                        .filter(|event| !logged.contains(&(event.date.clone(), event.name.clone())))
+                    );
+
+                    let mut map: BTreeMap<String, Vec<PkgEvent>> = BTreeMap::new();
+
+                    for event in events {
+                        map.entry(event.date.clone()).or_default().push(event);
+                    }
+
+                    for day_events in map.values_mut() {
+                        day_events.sort_by(|a, b| a.time.cmp(&b.time));
+                    }
+
+                    map
+                })
+                .await
+                .expect("Failed to complete task");
+
+                window.imp().events.replace(events);
+
+                window.update_marks();
+                window.update_view();
+            }
+        ));
+    }
+
+    //---------------------------------------
+    // Show window
+    //---------------------------------------
+    pub fn show(&self, pkg_model: &gio::ListStore) {
+        self.present();
+
+        glib::idle_add_local_once(clone!(
+            #[weak(rename_to = window)] self,
+            #[weak] pkg_model,
+            move || {
+                if !window.is_loaded() {
+                    window.populate(&pkg_model);
+
+                    window.set_is_loaded(true);
+                }
+            }
+        ));
+    }
+}
+
+impl Default for TimelineWindow {
+    //---------------------------------------
+    // Default constructor
+    //---------------------------------------
+    fn default() -> Self {
+        glib::Object::builder().build()
+    }
+}
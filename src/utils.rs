@@ -19,13 +19,21 @@ use which::which_global;
 use tokio::runtime::Runtime;
 use tokio::fs::File;
 use tokio::io::{AsyncWriteExt, AsyncReadExt};
-use tokio_util::io::StreamReader;
 use tokio_util::sync::CancellationToken;
-use futures_util::TryStreamExt;
+use futures_util::StreamExt;
+use reqwest::{header, StatusCode};
 use async_compression::tokio::bufread::GzipDecoder;
 use configparser::ini::Ini;
 use regex::Regex;
 
+use crate::{
+    APP_ID,
+    aur_helper::AurHelper,
+    aur_db::AurDb,
+    aur_page::AurPage,
+    srcinfo::SrcInfo
+};
+
 //------------------------------------------------------------------------------
 // STRUCT: Paths
 //------------------------------------------------------------------------------
@@ -33,25 +41,36 @@ pub struct Paths;
 
 impl Paths {
     //---------------------------------------
-    // Paru path function
+    // Paccat path function
     //---------------------------------------
-    pub fn paru() -> &'static which::Result<PathBuf> {
-        static PARU_PATH: LazyLock<which::Result<PathBuf>> = LazyLock::new(|| {
-            which_global("paru")
+    pub fn paccat() -> &'static which::Result<PathBuf> {
+        static PACCAT_PATH: LazyLock<which::Result<PathBuf>> = LazyLock::new(|| {
+            which_global("paccat")
         });
 
-        &PARU_PATH
+        &PACCAT_PATH
     }
 
     //---------------------------------------
-    // Paccat path function
+    // Git path function
     //---------------------------------------
-    pub fn paccat() -> &'static which::Result<PathBuf> {
-        static PACCAT_PATH: LazyLock<which::Result<PathBuf>> = LazyLock::new(|| {
-            which_global("paccat")
+    pub fn git() -> &'static which::Result<PathBuf> {
+        static GIT_PATH: LazyLock<which::Result<PathBuf>> = LazyLock::new(|| {
+            which_global("git")
         });
 
-        &PACCAT_PATH
+        &GIT_PATH
+    }
+
+    //---------------------------------------
+    // Gpg path function
+    //---------------------------------------
+    pub fn gpg() -> &'static which::Result<PathBuf> {
+        static GPG_PATH: LazyLock<which::Result<PathBuf>> = LazyLock::new(|| {
+            which_global("gpg")
+        });
+
+        &GPG_PATH
     }
 
     //---------------------------------------
@@ -158,13 +177,20 @@ impl ParuConf {
             .unwrap_or_default()
     }
 
+    //---------------------------------------
+    // Repo directory function
+    //---------------------------------------
+    pub fn repo_dir(repo: &str) -> PathBuf {
+        glib::user_cache_dir().join(format!("paru/clone/repo/{repo}"))
+    }
+
     //---------------------------------------
     // Local pkg map functions
     //---------------------------------------
     pub fn local_pkg_map() -> HashMap<String, Rc<String>> {
         Self::repo_names().into_iter()
             .flat_map(|repo| {
-                let path = glib::user_cache_dir().join(format!("paru/clone/repo/{repo}"));
+                let path = Self::repo_dir(&repo);
                 let repo_rc = Rc::new(repo);
 
                 WalkDir::new(path)
@@ -186,26 +212,31 @@ pub struct AurDBFile;
 
 impl AurDBFile {
     //---------------------------------------
-    // Path function
+    // Path functions
     //---------------------------------------
-    pub fn path() -> Option<&'static PathBuf> {
-        static AUR_FILE: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
+    pub fn cache_dir() -> Option<&'static PathBuf> {
+        static CACHE_DIR: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
             let cache_dir = glib::user_cache_dir().join("pacview");
 
             fs::create_dir_all(&cache_dir)
-                .map(|()| cache_dir.join("aur_packages"))
+                .map(|()| cache_dir)
                 .ok()
         });
 
-        AUR_FILE.as_ref()
+        CACHE_DIR.as_ref()
+    }
+
+    pub fn path() -> Option<PathBuf> {
+        Self::cache_dir()
+            .map(|cache_dir| cache_dir.join(AurDb::NAMES_FILE))
     }
 
     //---------------------------------------
     // Not found function
     //---------------------------------------
     pub fn not_found() -> bool {
-        Self::path()
-            .is_some_and(|aur_file| fs::metadata(aur_file).is_err())
+        Self::cache_dir()
+            .is_some_and(|cache_dir| !AurDb::exists(cache_dir) || Self::path().is_none_or(|aur_file| !aur_file.is_file()))
     }
 
     //---------------------------------------
@@ -236,35 +267,196 @@ impl AurDBFile {
         file_age.is_none_or(|age| age >= Duration::from_hours(max_age))
     }
 
+    //---------------------------------------
+    // Touch function
+    //---------------------------------------
+    fn touch(path: &Path) -> io::Result<()> {
+        fs::File::options()
+            .append(true)
+            .open(path)?
+            .set_modified(std::time::SystemTime::now())
+    }
+
     //---------------------------------------
     // Download async function
     //---------------------------------------
-    pub async fn download() -> Result<(), io::Error> {
-        let aur_file = Self::path()
-            .ok_or_else(|| io::Error::other("Failed to retrieve AUR database path"))?;
+    pub async fn download(aur_url: &str, cache_dir: &Path, token: CancellationToken, progress: Option<async_channel::Sender<(u64, Option<u64>)>>) -> io::Result<()> {
+        // Metadata dump including dependencies (extended version)
+        let url = format!("{}/packages-meta-ext-v1.json.gz", aur_url.trim_end_matches('/'));
+
+        let cache_dir = cache_dir.to_owned();
 
-        // Spawn tokio task to download AUR file
+        let names_file = cache_dir.join(AurDb::NAMES_FILE);
+
+        let part_file = cache_dir.join("aur_meta.json.gz.part");
+        let part_validators_file = cache_dir.join("aur_meta.json.gz.part.validators");
+        let gz_file = cache_dir.join("aur_meta.json.gz");
+        let dump_file = cache_dir.join("aur_meta.json");
+        let validators_file = cache_dir.join("aur_meta.validators");
+
+        // Spawn tokio task to download AUR metadata dump
         TokioUtils::runtime().spawn(
             async move {
-                let response = reqwest::Client::new()
-                    .get("https://aur.archlinux.org/packages.gz")
-                    .timeout(Duration::from_secs(5))
-                    .send()
-                    .await
-                    .map_err(io::Error::other)?;
+                let request = async {
+                    let client = reqwest::Client::builder()
+                        .connect_timeout(Duration::from_secs(5))
+                        .read_timeout(Duration::from_secs(30))
+                        .build()
+                        .map_err(io::Error::other)?;
 
-                let stream = response
-                    .bytes_stream()
-                    .map_err(io::Error::other);
+                    let mut request = client.get(&url);
 
-                let stream_reader = StreamReader::new(stream);
-                let mut decoder = GzipDecoder::new(stream_reader);
+                    // Only download if changed since last successful download
+                    if AurDb::exists(&cache_dir) && names_file.is_file() {
+                        let validators = HttpValidators::load(&validators_file);
 
-                let mut out_file = File::create(aur_file).await?;
+                        if let Some(etag) = &validators.etag {
+                            request = request.header(header::IF_NONE_MATCH, etag);
+                        }
+
+                        if let Some(last_modified) = &validators.last_modified {
+                            request = request.header(header::IF_MODIFIED_SINCE, last_modified);
+                        }
+                    }
 
-                tokio::io::copy(&mut decoder, &mut out_file).await?;
+                    // Resume interrupted download if the server still has the same file
+                    let part_validators = HttpValidators::load(&part_validators_file);
 
-                Ok(())
+                    let resume_from = match (fs::metadata(&part_file), part_validators.if_range()) {
+                        (Ok(metadata), Some(if_range)) if metadata.len() > 0 => {
+                            request = request
+                                .header(header::RANGE, format!("bytes={}-", metadata.len()))
+                                .header(header::IF_RANGE, if_range);
+
+                            metadata.len()
+                        },
+                        _ => 0
+                    };
+
+                    let response = request.send().await
+                        .map_err(io::Error::other)?;
+
+                    // Discard partial file if it cannot be resumed
+                    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE {
+                        let _ = fs::remove_file(&part_file);
+                        let _ = fs::remove_file(&part_validators_file);
+                    }
+
+                    let response = response.error_for_status()
+                        .map_err(io::Error::other)?;
+
+                    // Database is up to date
+                    if response.status() == StatusCode::NOT_MODIFIED {
+                        let _ = fs::remove_file(&part_file);
+                        let _ = fs::remove_file(&part_validators_file);
+
+                        return Self::touch(&names_file)
+                    }
+
+                    let (mut out_file, mut received) = if response.status() == StatusCode::PARTIAL_CONTENT {
+                        let out_file = File::options().append(true).open(&part_file).await?;
+
+                        (out_file, resume_from)
+                    } else {
+                        HttpValidators::from_response(&response).save(&part_validators_file)?;
+
+                        (File::create(&part_file).await?, 0)
+                    };
+
+                    let total = response.content_length().map(|length| length + received);
+
+                    // Download to partial file
+                    let mut stream = response.bytes_stream();
+
+                    while let Some(chunk) = stream.next().await {
+                        let chunk = chunk.map_err(io::Error::other)?;
+
+                        out_file.write_all(&chunk).await?;
+
+                        received += chunk.len() as u64;
+
+                        if let Some(sender) = &progress {
+                            let _ = sender.try_send((received, total));
+                        }
+                    }
+
+                    out_file.flush().await?;
+
+                    drop(out_file);
+
+                    tokio::fs::rename(&part_file, &gz_file).await?;
+
+                    Ok(())
+                };
+
+                tokio::select! {
+                    result = request => result?,
+                    () = token.cancelled() => {
+                        return Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled by user"))
+                    }
+                }
+
+                if !gz_file.exists() {
+                    return Ok(())
+                }
+
+                // Decompress metadata dump
+                let decompress = async {
+                    let gz_reader = tokio::io::BufReader::new(File::open(&gz_file).await?);
+                    let mut decoder = GzipDecoder::new(gz_reader);
+
+                    let mut out_file = File::create(&dump_file).await?;
+
+                    tokio::io::copy(&mut decoder, &mut out_file).await?;
+
+                    out_file.flush().await
+                };
+
+                let decompress_result = tokio::select! {
+                    result = decompress => result,
+                    () = token.cancelled() => Ok(())
+                };
+
+                // Do not build database if cancelled during decompression
+                let decompress_result = decompress_result.and_then(|()| {
+                    if token.is_cancelled() {
+                        Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled by user"))
+                    } else {
+                        Ok(())
+                    }
+                });
+
+                if let Err(error) = decompress_result {
+                    for file in [&dump_file, &gz_file, &part_validators_file] {
+                        let _ = fs::remove_file(file);
+                    }
+
+                    return Err(error)
+                }
+
+                // Build indexed AUR database from metadata dump
+                let result = tokio::task::spawn_blocking(move || {
+                    let result = fs::File::open(&dump_file)
+                        .and_then(|dump| AurDb::build(dump, &cache_dir));
+
+                    let _ = fs::remove_file(&dump_file);
+                    let _ = fs::remove_file(&gz_file);
+
+                    // Keep validators for conditional requests on success
+                    if result.is_ok() {
+                        fs::rename(&part_validators_file, &validators_file)?;
+                    } else {
+                        let _ = fs::remove_file(&part_validators_file);
+                    }
+
+                    result
+                })
+                .await
+                .map_err(io::Error::other)?;
+
+                AurDb::invalidate();
+
+                result.map(|_| ())
             }
         )
         .await
@@ -272,6 +464,397 @@ impl AurDBFile {
     }
 }
 
+//------------------------------------------------------------------------------
+// STRUCT: HttpValidators
+//------------------------------------------------------------------------------
+#[derive(Default, Debug)]
+struct HttpValidators {
+    etag: Option<String>,
+    last_modified: Option<String>
+}
+
+impl HttpValidators {
+    //---------------------------------------
+    // Constructors
+    //---------------------------------------
+    fn from_response(response: &reqwest::Response) -> Self {
+        let header_value = |name: header::HeaderName| {
+            response.headers().get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned)
+        };
+
+        Self {
+            etag: header_value(header::ETAG),
+            last_modified: header_value(header::LAST_MODIFIED)
+        }
+    }
+
+    fn load(path: &Path) -> Self {
+        let content = fs::read_to_string(path).unwrap_or_default();
+
+        let mut validators = Self::default();
+
+        for (key, value) in content.lines().filter_map(|line| line.split_once(": ")) {
+            match key {
+                "ETag" => validators.etag = Some(value.to_owned()),
+                "Last-Modified" => validators.last_modified = Some(value.to_owned()),
+                _ => {}
+            }
+        }
+
+        validators
+    }
+
+    //---------------------------------------
+    // Save function
+    //---------------------------------------
+    fn save(&self, path: &Path) -> io::Result<()> {
+        let mut content = String::new();
+
+        if let Some(etag) = &self.etag {
+            content.push_str(&format!("ETag: {etag}\n"));
+        }
+
+        if let Some(last_modified) = &self.last_modified {
+            content.push_str(&format!("Last-Modified: {last_modified}\n"));
+        }
+
+        fs::write(path, content)
+    }
+
+    //---------------------------------------
+    // If-Range value function
+    //---------------------------------------
+    fn if_range(&self) -> Option<&str> {
+        // Weak ETags cannot be used for range requests
+        self.etag.as_deref()
+            .filter(|etag| !etag.starts_with("W/"))
+            .or(self.last_modified.as_deref())
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: RepoMapFile
+//------------------------------------------------------------------------------
+pub struct RepoMapFile;
+
+impl RepoMapFile {
+    //---------------------------------------
+    // Path function
+    //---------------------------------------
+    fn path() -> Option<&'static PathBuf> {
+        static REPO_FILE: LazyLock<Option<PathBuf>> = LazyLock::new(|| {
+            let cache_dir = glib::user_cache_dir().join("pacview");
+
+            fs::create_dir_all(&cache_dir)
+                .map(|()| cache_dir.join("local_repos"))
+                .ok()
+        });
+
+        REPO_FILE.as_ref()
+    }
+
+    //---------------------------------------
+    // Load function
+    //---------------------------------------
+    pub fn load() -> HashMap<String, String> {
+        Self::path()
+            .and_then(|repo_file| fs::read_to_string(repo_file).ok())
+            .map(|content| {
+                content.lines()
+                    .filter_map(|line| line.split_once('\t'))
+                    .map(|(name, repo)| (name.to_owned(), repo.to_owned()))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    //---------------------------------------
+    // Save function
+    //---------------------------------------
+    pub fn save<'a>(repo_map: impl Iterator<Item = (&'a str, &'a str)>) {
+        if let Some(repo_file) = Self::path() {
+            let content: String = repo_map
+                .map(|(name, repo)| format!("{name}\t{repo}\n"))
+                .collect();
+
+            let _ = fs::write(repo_file, content);
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// STRUCT: PkgbuildSource
+//------------------------------------------------------------------------------
+pub struct PkgbuildSource;
+
+impl PkgbuildSource {
+    const REPO_URL: &str = "https://gitlab.archlinux.org/archlinux/packaging/packages";
+
+    //---------------------------------------
+    // Setting helper function
+    //---------------------------------------
+    pub fn setting(key: &str, default: &str) -> String {
+        let value = gio::Settings::new(APP_ID).string(key);
+
+        let value = value.trim().trim_end_matches('/');
+
+        if value.is_empty() { default.to_owned() } else { value.to_owned() }
+    }
+
+    //---------------------------------------
+    // Base URL functions
+    //---------------------------------------
+    pub fn aur_url() -> String {
+        AurPage::url() + "/cgit/aur.git"
+    }
+
+    pub fn repo_url() -> String {
+        Self::setting("pkgbuild-repo-url", Self::REPO_URL)
+    }
+
+    //---------------------------------------
+    // Is official repo function
+    //---------------------------------------
+    pub fn is_official_repo(repo: &str) -> bool {
+        Pacman::config().repos.iter().any(|r| r.name == repo)
+    }
+
+    //---------------------------------------
+    // GitLab project name function
+    //---------------------------------------
+    pub fn gitlab_project(base: &str) -> String {
+        static PLUS_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"([a-zA-Z0-9]+)\+([a-zA-Z]+)").expect("Failed to compile Regex")
+        });
+
+        static INVALID_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"[^a-zA-Z0-9_\-.]").expect("Failed to compile Regex")
+        });
+
+        static SEP_EXPR: LazyLock<Regex> = LazyLock::new(|| {
+            Regex::new(r"[_\-]{2,}").expect("Failed to compile Regex")
+        });
+
+        // Same conversion as used by the Arch Linux packaging tools
+        if base == "tree" {
+            return String::from("unix-tree");
+        }
+
+        let project = PLUS_EXPR.replace_all(base, "$1-$2");
+        let project = project.replace('+', "plus");
+        let project = INVALID_EXPR.replace_all(&project, "-");
+
+        SEP_EXPR.replace_all(&project, "-").into_owned()
+    }
+
+    //---------------------------------------
+    // Raw URL function
+    //---------------------------------------
+    pub fn raw_url(repo: &str, base: &str, file: &str) -> Option<String> {
+        if repo == "aur" {
+            Some(Self::aur_raw_url(&Self::aur_url(), base, file))
+        } else if Self::is_official_repo(repo) {
+            Some(Self::repo_raw_url(&Self::repo_url(), base, file))
+        } else {
+            None
+        }
+    }
+
+    fn aur_raw_url(aur_url: &str, base: &str, file: &str) -> String {
+        format!("{aur_url}/plain/{file}?h={base}")
+    }
+
+    fn repo_raw_url(repo_url: &str, base: &str, file: &str) -> String {
+        format!("{repo_url}/{}/-/raw/main/{file}", Self::gitlab_project(base))
+    }
+
+    //---------------------------------------
+    // Local clone directory function
+    //---------------------------------------
+    pub fn clone_dir(repo: &str, base: &str) -> Option<PathBuf> {
+        let clone_dir = gio::Settings::new(APP_ID).string("pkgbuild-clone-dir");
+
+        let candidates = if clone_dir.is_empty() {
+            // Default to AUR helper clone directories
+            AurHelper::active().clone_dirs(repo, base)
+        } else {
+            let clone_dir = PathBuf::from(clone_dir.as_str());
+
+            vec![clone_dir.join(repo).join(base), clone_dir.join(base)]
+        };
+
+        candidates.into_iter()
+            .find(|dir| dir.join("PKGBUILD").is_file())
+    }
+
+    //---------------------------------------
+    // Local clone path function
+    //---------------------------------------
+    pub fn clone_path(repo: &str, base: &str, file: &str) -> Option<PathBuf> {
+        Self::clone_dir(repo, base)
+            .map(|dir| dir.join(file))
+            .filter(|path| path.is_file())
+    }
+
+    //---------------------------------------
+    // Is available function
+    //---------------------------------------
+    pub fn is_available(repo: &str, base: &str) -> bool {
+        repo == "aur" || Self::is_official_repo(repo)
+            || Self::clone_path(repo, base, "PKGBUILD").is_some()
+            || (repo != "local" && AurHelper::active().can_print_pkgbuild())
+    }
+
+    //---------------------------------------
+    // Fetch async function
+    //---------------------------------------
+    pub async fn fetch(repo: &str, base: &str, file: &str, token: CancellationToken) -> io::Result<String> {
+        // Download from AUR or Arch Linux GitLab
+        let download_error = if let Some(url) = Self::raw_url(repo, base, file) {
+            match TokioUtils::fetch(&url, Some(token.clone())).await {
+                Ok(text) => return Ok(text),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => return Err(error),
+                Err(error) => Some(error)
+            }
+        } else {
+            None
+        };
+
+        // Fall back to local clone
+        if let Some(path) = Self::clone_path(repo, base, file) {
+            return tokio::fs::read_to_string(path).await;
+        }
+
+        // Fall back to AUR helper
+        let helper = AurHelper::active();
+
+        if file == "PKGBUILD" && repo != "local"
+            && let Some(helper_path) = helper.path()
+            && let Some(args) = helper.pkgbuild_args(base) {
+                return match TokioUtils::run(helper_path, args, Some(token)).await? {
+                    (Some(0), pkgbuild) => Ok(pkgbuild),
+                    _ => Err(io::Error::other(format!("{} error", helper.as_ref())))
+                };
+            }
+
+        Err(download_error.unwrap_or_else(|| io::Error::new(io::ErrorKind::NotFound, format!("{file} not found"))))
+    }
+
+    //---------------------------------------
+    // List source files async function
+    //---------------------------------------
+    pub async fn list_files(repo: &str, base: &str, token: CancellationToken) -> Vec<String> {
+        let mut files = vec![String::from("PKGBUILD")];
+
+        // Use tracked files from local clone
+        if let Some(dir) = Self::clone_dir(repo, base)
+            && let Ok(git_path) = Paths::git() {
+                let dir = dir.display().to_string();
+
+                if let Ok((Some(0), output)) = TokioUtils::run(git_path, ["-C", dir.as_str(), "ls-files"], Some(token.clone())).await {
+                    files.extend(output.lines()
+                        .filter(|&file| file != "PKGBUILD" && file != ".gitignore")
+                        .map(ToOwned::to_owned)
+                    );
+
+                    return files;
+                }
+            }
+
+        // Otherwise get local source files from .SRCINFO
+        if let Ok(srcinfo) = Self::fetch(repo, base, ".SRCINFO", token).await {
+            files.push(String::from(".SRCINFO"));
+
+            if let Some(info) = SrcInfo::parse(&srcinfo) {
+                files.extend(info.local_files());
+            }
+        }
+
+        files
+    }
+
+    //---------------------------------------
+    // Fetch from local clone history function
+    //---------------------------------------
+    async fn fetch_clone_version(repo: &str, base: &str, version: &str, file: &str, token: CancellationToken) -> io::Result<String> {
+        let dir = Self::clone_dir(repo, base)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Local clone not found"))?;
+
+        let git_path = Paths::git().as_ref()
+            .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "git not found"))?;
+
+        let dir = dir.display().to_string();
+
+        let log = match TokioUtils::run(git_path, ["-C", dir.as_str(), "log", "--format=%H", "--", ".SRCINFO"], Some(token.clone())).await? {
+            (Some(0), log) => log,
+            _ => return Err(io::Error::other("Failed to read local clone history"))
+        };
+
+        for commit in log.lines() {
+            let srcinfo_spec = format!("{commit}:.SRCINFO");
+
+            let (_, srcinfo) = TokioUtils::run(git_path, ["-C", dir.as_str(), "show", srcinfo_spec.as_str()], Some(token.clone())).await?;
+
+            if SrcInfo::parse(&srcinfo).is_some_and(|info| info.version() == version) {
+                let file_spec = format!("{commit}:{file}");
+
+                return match TokioUtils::run(git_path, ["-C", dir.as_str(), "show", file_spec.as_str()], Some(token)).await? {
+                    (Some(0), text) => Ok(text),
+                    _ => Err(io::Error::new(io::ErrorKind::NotFound, format!("{file} not found in commit {commit}")))
+                };
+            }
+        }
+
+        Err(io::Error::new(io::ErrorKind::NotFound, format!("Version {version} not found in local clone")))
+    }
+
+    //---------------------------------------
+    // Fetch version async function
+    //---------------------------------------
+    pub async fn fetch_version(repo: &str, base: &str, version: &str, file: &str, token: CancellationToken) -> io::Result<String> {
+        const AUR_MAX_DEPTH: usize = 10;
+
+        // Try local clone history
+        let clone_error = match Self::fetch_clone_version(repo, base, version, file, token.clone()).await {
+            Ok(text) => return Ok(text),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => return Err(error),
+            Err(error) => error
+        };
+
+        if Self::is_official_repo(repo) {
+            // Arch Linux GitLab tags releases by version (with ':' replaced by '-')
+            let url = format!("{}/{}/-/raw/{}/{file}", Self::repo_url(), Self::gitlab_project(base), version.replace(':', "-"));
+
+            return TokioUtils::fetch(&url, Some(token)).await;
+        }
+
+        if repo == "aur" {
+            // Walk AUR git history until .SRCINFO version matches
+            let aur_url = Self::aur_url();
+
+            for depth in 0..AUR_MAX_DEPTH {
+                let srcinfo_url = format!("{aur_url}/plain/.SRCINFO?h={base}&id={base}~{depth}");
+
+                let srcinfo = match TokioUtils::fetch(&srcinfo_url, Some(token.clone())).await {
+                    Ok(srcinfo) => srcinfo,
+                    Err(error) if error.kind() == io::ErrorKind::Interrupted => return Err(error),
+                    Err(_) => break
+                };
+
+                if SrcInfo::parse(&srcinfo).is_some_and(|info| info.version() == version) {
+                    let url = format!("{aur_url}/plain/{file}?h={base}&id={base}~{depth}");
+
+                    return TokioUtils::fetch(&url, Some(token)).await;
+                }
+            }
+        }
+
+        Err(clone_error)
+    }
+}
+
 //------------------------------------------------------------------------------
 // STRUCT: TokioUtils
 //------------------------------------------------------------------------------
@@ -377,6 +960,48 @@ impl TokioUtils {
         .expect("Failed to complete tokio task")
     }
 
+    //---------------------------------------
+    // Fetch function
+    //---------------------------------------
+    pub async fn fetch(url: &str, token: Option<CancellationToken>) -> io::Result<String> {
+        let url_owned = url.to_owned();
+
+        Self::runtime().spawn(
+            async move {
+                let request = async {
+                    reqwest::Client::new()
+                        .get(url_owned)
+                        .timeout(Duration::from_secs(10))
+                        .send()
+                        .await
+                        .and_then(reqwest::Response::error_for_status)
+                        .map_err(io::Error::other)?
+                        .text()
+                        .await
+                        .map_err(io::Error::other)
+                };
+
+                // Resolve cancellation token
+                let cancellation_future = async {
+                    if let Some(token) = token {
+                        token.cancelled().await;
+                    } else {
+                        std::future::pending::<()>().await;
+                    }
+                };
+
+                tokio::select! {
+                    result = request => result,
+                    () = cancellation_future => {
+                        Err(io::Error::new(io::ErrorKind::Interrupted, "Cancelled by user"))
+                    }
+                }
+            }
+        )
+        .await
+        .expect("Failed to complete tokio task")
+    }
+
     //---------------------------------------
     // Spawn pipe stdin function
     //---------------------------------------
@@ -513,3 +1138,318 @@ impl StyleSchemes {
                 .is_some_and(|variant_id| variant_id == id)
     }
 }
+
+//------------------------------------------------------------------------------
+// TESTS
+//------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use async_compression::tokio::bufread::GzipEncoder;
+
+    use crate::test_utils::{TestDir, TestRequest, TestServer, TestResponse};
+
+    // Subset of packages-meta-ext-v1.json from the AUR
+    const DUMP: &str = include_str!("../tests/fixtures/packages-meta-ext-v1.json");
+    const DUMP_PATH: &str = "/packages-meta-ext-v1.json.gz";
+    const ETAG: &str = "\"6752a1f0-1b2c3d\"";
+    const LAST_MODIFIED: &str = "Fri, 06 Dec 2024 07:00:00 GMT";
+
+    #[test]
+    fn gitlab_project_converts_plus_signs() {
+        assert_eq!(PkgbuildSource::gitlab_project("gtk+"), "gtkplus");
+        assert_eq!(PkgbuildSource::gitlab_project("libsigc++"), "libsigcplusplus");
+        assert_eq!(PkgbuildSource::gitlab_project("dvd+rw-tools"), "dvd-rw-tools");
+        assert_eq!(PkgbuildSource::gitlab_project("libxml++2.6"), "libxmlplusplus2.6");
+    }
+
+    #[test]
+    fn gitlab_project_keeps_dots_and_leading_digits() {
+        assert_eq!(PkgbuildSource::gitlab_project("python-zope.interface"), "python-zope.interface");
+        assert_eq!(PkgbuildSource::gitlab_project("0ad"), "0ad");
+        assert_eq!(PkgbuildSource::gitlab_project("389-ds-base"), "389-ds-base");
+    }
+
+    #[test]
+    fn gitlab_project_handles_special_names() {
+        assert_eq!(PkgbuildSource::gitlab_project("tree"), "unix-tree");
+        assert_eq!(PkgbuildSource::gitlab_project("foo__bar"), "foo-bar");
+        assert_eq!(PkgbuildSource::gitlab_project("foo@bar"), "foo-bar");
+    }
+
+    #[test]
+    fn raw_urls() {
+        assert_eq!(
+            PkgbuildSource::aur_raw_url("https://aur.archlinux.org/cgit/aur.git", "paru", "PKGBUILD"),
+            "https://aur.archlinux.org/cgit/aur.git/plain/PKGBUILD?h=paru"
+        );
+
+        assert_eq!(
+            PkgbuildSource::repo_raw_url("https://gitlab.archlinux.org/archlinux/packaging/packages", "gtk+", ".SRCINFO"),
+            "https://gitlab.archlinux.org/archlinux/packaging/packages/gtkplus/-/raw/main/.SRCINFO"
+        );
+    }
+
+    #[test]
+    fn fetch_raw_url_from_local_server() {
+        let server = TestServer::start(|request| {
+            match request.path.as_str() {
+                "/packages/gtkplus/-/raw/main/PKGBUILD" => TestResponse::ok("pkgname=gtk+"),
+                _ => TestResponse::not_found()
+            }
+        });
+
+        let runtime = TokioUtils::runtime();
+
+        let url = PkgbuildSource::repo_raw_url(&format!("{}/packages", server.url()), "gtk+", "PKGBUILD");
+        let text = runtime.block_on(TokioUtils::fetch(&url, None)).unwrap();
+
+        assert_eq!(text, "pkgname=gtk+");
+
+        let url = PkgbuildSource::repo_raw_url(&format!("{}/packages", server.url()), "missing", "PKGBUILD");
+
+        assert!(runtime.block_on(TokioUtils::fetch(&url, None)).is_err());
+    }
+
+    #[test]
+    fn fetch_cancelled_returns_interrupted() {
+        let server = TestServer::start(|_| {
+            TestResponse::ok(vec![b'x'; 64 * 1024]).slow(Duration::from_millis(50))
+        });
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let error = TokioUtils::runtime()
+            .block_on(TokioUtils::fetch(&server.url(), Some(token)))
+            .unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+    }
+
+    fn gzip(data: &[u8]) -> Vec<u8> {
+        let mut gz = vec![];
+
+        TokioUtils::runtime()
+            .block_on(GzipEncoder::new(data).read_to_end(&mut gz))
+            .unwrap();
+
+        gz
+    }
+
+    fn dump_server(requests: &Arc<Mutex<Vec<TestRequest>>>) -> (TestServer, Arc<Vec<u8>>) {
+        let gz = Arc::new(gzip(DUMP.as_bytes()));
+
+        let server = TestServer::start({
+            let gz = Arc::clone(&gz);
+            let requests = Arc::clone(requests);
+
+            move |request| {
+                requests.lock().unwrap().push(request.clone());
+
+                if request.path != DUMP_PATH {
+                    return TestResponse::not_found()
+                }
+
+                if request.header("If-None-Match") == Some(ETAG) {
+                    return TestResponse::new(304, "")
+                }
+
+                let start = request.header("Range")
+                    .and_then(|range| range.strip_prefix("bytes="))
+                    .and_then(|range| range.strip_suffix('-'))
+                    .and_then(|start| start.parse::<usize>().ok());
+
+                match start {
+                    Some(start) if start >= gz.len() => {
+                        TestResponse::new(416, "")
+                            .header("Content-Range", &format!("bytes */{}", gz.len()))
+                    },
+                    Some(start) if request.header("If-Range") == Some(ETAG) => {
+                        TestResponse::new(206, gz[start..].to_vec())
+                            .header("Content-Range", &format!("bytes {start}-{}/{}", gz.len() - 1, gz.len()))
+                    },
+                    _ => {
+                        TestResponse::ok(gz.to_vec())
+                            .header("ETag", ETAG)
+                            .header("Last-Modified", LAST_MODIFIED)
+                    }
+                }
+            }
+        });
+
+        (server, gz)
+    }
+
+    fn download(server: &TestServer, dir: &TestDir, token: CancellationToken) -> io::Result<()> {
+        TokioUtils::runtime().block_on(AurDBFile::download(&server.url(), dir.path(), token, None))
+    }
+
+    fn assert_database(dir: &TestDir) {
+        let aur_db = AurDb::open(dir.path()).unwrap();
+
+        let pkgs = aur_db.info(&["yay", "paru"]).unwrap();
+
+        assert_eq!(pkgs.iter().map(|pkg| pkg.name.as_str()).collect::<Vec<_>>(), ["yay", "paru"]);
+
+        // Temporary download files are removed
+        for file in ["aur_meta.json.gz.part", "aur_meta.json.gz.part.validators", "aur_meta.json.gz", "aur_meta.json"] {
+            assert!(!dir.path().join(file).exists(), "{file} exists");
+        }
+    }
+
+    #[test]
+    fn download_builds_database() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let (server, _) = dump_server(&requests);
+
+        let dir = TestDir::new("aur-download");
+
+        download(&server, &dir, CancellationToken::new()).unwrap();
+
+        assert_database(&dir);
+
+        assert_eq!(requests.lock().unwrap()[0].header("Range"), None);
+        assert_eq!(requests.lock().unwrap()[0].header("If-None-Match"), None);
+
+        let validators = HttpValidators::load(&dir.path().join("aur_meta.validators"));
+
+        assert_eq!(validators.etag.as_deref(), Some(ETAG));
+        assert_eq!(validators.last_modified.as_deref(), Some(LAST_MODIFIED));
+    }
+
+    #[test]
+    fn download_not_modified_keeps_database() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let (server, _) = dump_server(&requests);
+
+        let dir = TestDir::new("aur-download-304");
+
+        download(&server, &dir, CancellationToken::new()).unwrap();
+        download(&server, &dir, CancellationToken::new()).unwrap();
+
+        let requests = requests.lock().unwrap();
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[1].header("If-None-Match"), Some(ETAG));
+        assert_eq!(requests[1].header("If-Modified-Since"), Some(LAST_MODIFIED));
+
+        assert_database(&dir);
+    }
+
+    #[test]
+    fn download_resumes_partial_file() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let (server, gz) = dump_server(&requests);
+
+        let dir = TestDir::new("aur-download-206");
+
+        fs::write(dir.path().join("aur_meta.json.gz.part"), &gz[..100]).unwrap();
+
+        HttpValidators { etag: Some(ETAG.to_owned()), last_modified: None }
+            .save(&dir.path().join("aur_meta.json.gz.part.validators"))
+            .unwrap();
+
+        download(&server, &dir, CancellationToken::new()).unwrap();
+
+        let requests = requests.lock().unwrap();
+
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].header("Range"), Some("bytes=100-"));
+        assert_eq!(requests[0].header("If-Range"), Some(ETAG));
+
+        assert_database(&dir);
+    }
+
+    #[test]
+    fn download_discards_unsatisfiable_partial_file() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let (server, gz) = dump_server(&requests);
+
+        let dir = TestDir::new("aur-download-416");
+
+        let part_file = dir.path().join("aur_meta.json.gz.part");
+        let part_validators_file = dir.path().join("aur_meta.json.gz.part.validators");
+
+        fs::write(&part_file, [gz.as_slice(), b"stale"].concat()).unwrap();
+
+        HttpValidators { etag: Some(ETAG.to_owned()), last_modified: None }
+            .save(&part_validators_file)
+            .unwrap();
+
+        assert!(download(&server, &dir, CancellationToken::new()).is_err());
+
+        assert!(!part_file.exists());
+        assert!(!part_validators_file.exists());
+        assert!(!AurDb::exists(dir.path()));
+
+        // Next download starts from scratch
+        download(&server, &dir, CancellationToken::new()).unwrap();
+
+        assert_eq!(requests.lock().unwrap()[1].header("Range"), None);
+
+        assert_database(&dir);
+    }
+
+    #[test]
+    fn download_cancelled_returns_interrupted() {
+        let requests = Arc::new(Mutex::new(vec![]));
+        let (server, _) = dump_server(&requests);
+
+        let dir = TestDir::new("aur-download-cancel");
+
+        let token = CancellationToken::new();
+        token.cancel();
+
+        let error = download(&server, &dir, token).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::Interrupted);
+        assert!(!AurDb::exists(dir.path()));
+    }
+
+    #[test]
+    fn http_validators_round_trip() {
+        let dir = TestDir::new("http-validators");
+        let path = dir.path().join("validators");
+
+        // Missing file loads empty validators
+        let validators = HttpValidators::load(&path);
+
+        assert!(validators.etag.is_none() && validators.last_modified.is_none());
+        assert_eq!(validators.if_range(), None);
+
+        let validators = HttpValidators {
+            etag: Some(ETAG.to_owned()),
+            last_modified: Some(LAST_MODIFIED.to_owned())
+        };
+
+        validators.save(&path).unwrap();
+
+        let loaded = HttpValidators::load(&path);
+
+        assert_eq!(loaded.etag.as_deref(), Some(ETAG));
+        assert_eq!(loaded.last_modified.as_deref(), Some(LAST_MODIFIED));
+        assert_eq!(loaded.if_range(), Some(ETAG));
+    }
+
+    #[test]
+    fn http_validators_if_range_skips_weak_etag() {
+        let weak = HttpValidators {
+            etag: Some(String::from("W/\"abc\"")),
+            last_modified: Some(LAST_MODIFIED.to_owned())
+        };
+
+        assert_eq!(weak.if_range(), Some(LAST_MODIFIED));
+
+        let weak_only = HttpValidators { etag: Some(String::from("W/\"abc\"")), last_modified: None };
+
+        assert_eq!(weak_only.if_range(), None);
+
+        let last_modified_only = HttpValidators { etag: None, last_modified: Some(LAST_MODIFIED.to_owned()) };
+
+        assert_eq!(last_modified_only.if_range(), Some(LAST_MODIFIED));
+    }
+}
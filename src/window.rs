@@ -1,9 +1,11 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
+use std::cmp::Ordering;
 use std::sync::LazyLock;
 use std::path::{Path, PathBuf};
 use std::collections::{HashMap, HashSet};
 use std::time::Duration;
 use std::fs;
+use std::io;
 
 use gtk::{gio, glib, gdk};
 use adw::subclass::prelude::*;
@@ -22,20 +24,29 @@ use walkdir::WalkDir;
 use crate::{
     APP_ID,
     PacViewApplication,
-    pkg_data::{PkgFlags, PkgData},
+    pkg_data::{PkgFlags, PkgData, PkgOrigin},
     pkg_object::PkgObject,
     package_view::{PackageView, PackageViewState},
     info_pane::InfoPane,
     repo_item::RepoItem,
     status_item::{StatusItem, StatusItemState},
     stats_window::StatsWindow,
+    disk_usage_window::DiskUsageWindow,
+    rollback_window::RollbackWindow,
+    timeline_window::TimelineWindow,
+    compare_window::CompareWindow,
     backup_window::BackupWindow,
     groups_window::GroupsWindow,
+    health_window::HealthWindow,
+    keyring_window::KeyringWindow,
+    mirrors_window::MirrorsWindow,
     log_window::LogWindow,
     cache_window::CacheWindow,
     config_dialog::ConfigDialog,
     preferences_dialog::PreferencesDialog,
-    utils::{Paths, Pacman, ParuConf, AurDBFile, TokioUtils}
+    aur_helper::AurHelper,
+    aur_page::AurPage,
+    utils::{Pacman, AurDBFile, RepoMapFile, PkgbuildSource, TokioUtils}
 };
 
 //------------------------------------------------------------------------------
@@ -89,6 +100,9 @@ mod imp {
 
         pub(super) update_cancel_token: RefCell<Option<CancellationToken>>,
 
+        pub(super) aur_download_token: RefCell<Option<CancellationToken>>,
+        pub(super) aur_download_cancelled: Cell<bool>,
+
         pub(super) notify_debouncer: RefCell<Option<Debouncer<INotifyWatcher, NoCache>>>,
 
         pub(super) prefs_dialog: RefCell<PreferencesDialog>,
@@ -96,8 +110,15 @@ mod imp {
         pub(super) backup_window: RefCell<BackupWindow>,
         pub(super) cache_window: RefCell<CacheWindow>,
         pub(super) groups_window: RefCell<GroupsWindow>,
+        pub(super) health_window: RefCell<HealthWindow>,
+        pub(super) keyring_window: RefCell<KeyringWindow>,
+        pub(super) mirrors_window: RefCell<MirrorsWindow>,
         pub(super) log_window: RefCell<LogWindow>,
         pub(super) stats_window: RefCell<StatsWindow>,
+        pub(super) disk_usage_window: RefCell<DiskUsageWindow>,
+        pub(super) rollback_window: RefCell<RollbackWindow>,
+        pub(super) timeline_window: RefCell<TimelineWindow>,
+        pub(super) compare_window: RefCell<CompareWindow>,
 
         pub(super) config_dialog: RefCell<ConfigDialog>,
      }
@@ -180,7 +201,7 @@ mod imp {
             klass.install_action_async("win.update-aur-database", None, async |window, _, _| {
                 let imp = window.imp();
 
-                if AurDBFile::path().is_some() {
+                if AurDBFile::path().is_some() && imp.aur_download_token.borrow().is_none() {
                     imp.update_item.borrow().set_state(StatusItemState::Reset);
                     imp.package_view.set_state(PackageViewState::AURDownload);
                     imp.info_pane.set_pkg(None::<PkgObject>);
@@ -188,8 +209,10 @@ mod imp {
 
                     window.cancel_package_updates();
 
-                    // Spawn tokio task to download AUR package names file
-                    let _ = AurDBFile::download().await;
+                    imp.aur_download_cancelled.set(false);
+
+                    // Spawn tokio task to download AUR metadata database
+                    let _ = window.download_aur_database().await;
 
                     // Refresh packages
                     gtk::prelude::WidgetExt::activate_action(&window, "win.refresh", None)
@@ -197,6 +220,17 @@ mod imp {
                 }
             });
 
+            // Cancel AUR database download action
+            klass.install_action("win.cancel-aur-download", None, |window, _, _| {
+                let imp = window.imp();
+
+                if let Some(token) = imp.aur_download_token.take() {
+                    token.cancel();
+
+                    imp.aur_download_cancelled.set(true);
+                }
+            });
+
             // Package view copy list action
             klass.install_action("win.copy-package-list", None, |window, _, _| {
                  window.imp().package_view.copy_list();
@@ -229,6 +263,22 @@ mod imp {
                 imp.groups_window.borrow().show(&imp.package_view.pkg_model());
             });
 
+            klass.install_action("win.show-health", None, |window, _, _| {
+                let imp = window.imp();
+
+                imp.health_window.borrow().show(&imp.package_view.pkg_model());
+            });
+
+            klass.install_action("win.show-pacman-keyring", None, |window, _, _| {
+                let imp = window.imp();
+
+                imp.keyring_window.borrow().show(&imp.package_view.pkg_model());
+            });
+
+            klass.install_action("win.show-pacman-mirrors", None, |window, _, _| {
+                window.imp().mirrors_window.borrow().show();
+            });
+
             klass.install_action("win.show-pacman-log", None, |window, _, _| {
                 window.imp().log_window.borrow().show();
             });
@@ -239,6 +289,39 @@ mod imp {
                 imp.stats_window.borrow().show(&imp.repo_names.borrow(), &imp.package_view.pkg_model());
             });
 
+            klass.install_action("win.show-disk-usage", None, |window, _, _| {
+                let imp = window.imp();
+
+                imp.disk_usage_window.borrow().show(&imp.package_view.pkg_model());
+            });
+
+            klass.install_action("win.show-timeline", None, |window, _, _| {
+                let imp = window.imp();
+
+                imp.timeline_window.borrow().show(&imp.package_view.pkg_model());
+            });
+
+            klass.install_action("win.show-compare", None, |window, _, _| {
+                window.imp().compare_window.borrow().show();
+            });
+
+            klass.install_action("win.compare-package", None, |window, _, _| {
+                let imp = window.imp();
+
+                if let Some(pkg) = imp.info_pane.pkg() {
+                    let compare_window = imp.compare_window.borrow();
+
+                    compare_window.add_pkg(&pkg);
+                    compare_window.show();
+                }
+            });
+
+            klass.install_action("win.show-rollback", None, |window, _, _| {
+                let imp = window.imp();
+
+                imp.rollback_window.borrow().show(&imp.package_view.pkg_model());
+            });
+
             klass.install_action("win.show-pacman-config", None, |window, _, _| {
                 window.imp().config_dialog.borrow().present(Some(window));
             });
@@ -334,6 +417,18 @@ mod imp {
             // Stats window key binding
             klass.add_binding_action(Key::S, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-stats");
 
+            // Disk usage window key binding
+            klass.add_binding_action(Key::D, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-disk-usage");
+
+            // Package timeline window key binding
+            klass.add_binding_action(Key::T, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-timeline");
+
+            // Package comparison window key binding
+            klass.add_binding_action(Key::X, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-compare");
+
+            // Rollback window key binding
+            klass.add_binding_action(Key::R, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-rollback");
+
             // Backup files window key binding
             klass.add_binding_action(Key::B, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-backup-files");
 
@@ -346,6 +441,15 @@ mod imp {
             // Pacman groups window key binding
             klass.add_binding_action(Key::G, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-pacman-groups");
 
+            // System health window key binding
+            klass.add_binding_action(Key::H, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-health");
+
+            // Pacman keyring window key binding
+            klass.add_binding_action(Key::K, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-pacman-keyring");
+
+            // Pacman mirrors window key binding
+            klass.add_binding_action(Key::M, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-pacman-mirrors");
+
             // Pacman config dialog key binding
             klass.add_binding_action(Key::P, ModifierType::CONTROL_MASK | ModifierType::SHIFT_MASK, "win.show-pacman-config");
 
@@ -368,6 +472,12 @@ mod imp {
                 Propagation::Stop
             });
 
+            klass.add_binding(Key::R, ModifierType::ALT_MASK, |window| {
+                window.imp().info_pane.set_active_tab("aur");
+
+                Propagation::Stop
+            });
+
             // Infopane previous/next key bindings
             klass.add_binding(Key::Left, ModifierType::ALT_MASK, |window| {
                 window.imp().info_pane.activate_action("info.previous", None).unwrap();
@@ -508,6 +618,9 @@ impl PacViewWindow {
                     "win.update-aur-database",
                     prefs_dialog.aur_database_download()
                 );
+
+                // Allow automatic download again after cancelled download
+                window.imp().aur_download_cancelled.set(false);
             }
         ));
     }
@@ -538,8 +651,15 @@ impl PacViewWindow {
         imp.backup_window.borrow().set_transient_for(Some(self));
         imp.cache_window.borrow().set_transient_for(Some(self));
         imp.groups_window.borrow().set_transient_for(Some(self));
+        imp.health_window.borrow().set_transient_for(Some(self));
+        imp.keyring_window.borrow().set_transient_for(Some(self));
+        imp.mirrors_window.borrow().set_transient_for(Some(self));
         imp.log_window.borrow().set_transient_for(Some(self));
         imp.stats_window.borrow().set_transient_for(Some(self));
+        imp.disk_usage_window.borrow().set_transient_for(Some(self));
+        imp.rollback_window.borrow().set_transient_for(Some(self));
+        imp.timeline_window.borrow().set_transient_for(Some(self));
+        imp.compare_window.borrow().set_transient_for(Some(self));
 
         // Bind preferences dialog properties to search bar
         let prefs_dialog = imp.prefs_dialog.borrow();
@@ -583,6 +703,8 @@ impl PacViewWindow {
 
         settings.bind("color-scheme", prefs_dialog, "color-scheme").build();
         settings.bind("infopane-width", prefs_dialog, "infopane-width").build();
+        settings.bind("aur-helper", prefs_dialog, "aur-helper").build();
+        settings.bind("aur-url", prefs_dialog, "aur-url").build();
         settings.bind("aur-database-download", prefs_dialog, "aur-database-download").build();
         settings.bind("aur-database-age", prefs_dialog, "aur-database-age").build();
         settings.bind("auto-refresh", prefs_dialog, "auto-refresh").build();
@@ -596,6 +718,8 @@ impl PacViewWindow {
         settings.bind("pkgbuild-style-scheme", prefs_dialog, "pkgbuild-style-scheme").build();
         settings.bind("pkgbuild-use-system-font", prefs_dialog, "pkgbuild-use-system-font").build();
         settings.bind("pkgbuild-custom-font", prefs_dialog, "pkgbuild-custom-font").build();
+        settings.bind("pkgbuild-repo-url", prefs_dialog, "pkgbuild-repo-url").build();
+        settings.bind("pkgbuild-clone-dir", prefs_dialog, "pkgbuild-clone-dir").build();
 
         // Load/save package view sort properties
         if prefs_dialog.remember_sort() {
@@ -674,7 +798,10 @@ impl PacViewWindow {
         // Create repo names list
         let repo_names: Vec<String> = pacman_config.repos.iter()
             .map(|r| r.name.clone())
-            .chain(ParuConf::repo_names())
+            .chain(
+                AurHelper::active().repo_names().into_iter()
+                    .filter(|repo| !pacman_config.repos.iter().any(|r| r.name == *repo))
+            )
             .chain(["aur", "local"].map(ToOwned::to_owned))
             .collect();
 
@@ -687,21 +814,30 @@ impl PacViewWindow {
         // Reset windows
         imp.backup_window.borrow().set_is_loaded(false);
         imp.cache_window.borrow().set_is_loaded(false);
+        imp.compare_window.borrow().clear();
         imp.groups_window.borrow().set_is_loaded(false);
+        imp.health_window.borrow().set_is_loaded(false);
+        imp.keyring_window.borrow().set_is_loaded(false);
+        imp.mirrors_window.borrow().set_is_loaded(false);
         imp.log_window.borrow().set_is_loaded(false);
         imp.stats_window.borrow().set_is_loaded(false);
+        imp.disk_usage_window.borrow().set_is_loaded(false);
+        imp.rollback_window.borrow().set_is_loaded(false);
+        imp.timeline_window.borrow().set_is_loaded(false);
 
         // If AUR database download is enabled and AUR file does not exist, download it
         let aur_download = imp.prefs_dialog.borrow().aur_database_download();
 
-        if aur_download && AurDBFile::not_found() {
+        if aur_download && AurDBFile::not_found() && !imp.aur_download_cancelled.get()
+            && imp.aur_download_token.borrow().is_none()
+        {
             imp.package_view.set_state(PackageViewState::AURDownload);
             imp.info_pane.set_pkg(None::<PkgObject>);
 
             glib::spawn_future_local(clone!(
                 #[weak(rename_to = window)] self,
                 async move {
-                    let _ = AurDBFile::download().await;
+                    let _ = window.download_aur_database().await;
 
                     window.alpm_load_packages(aur_download);
                 }
@@ -754,7 +890,7 @@ impl PacViewWindow {
 
                 let item = StatusItem::new(&format!("status-{nick}-symbolic"), f.name(), flag);
 
-                if flag == PkgFlags::UPDATES {
+                if flag.intersects(PkgFlags::UPDATES | PkgFlags::NEWER | PkgFlags::DROPPED | PkgFlags::MOVED | PkgFlags::ORPHANED | PkgFlags::OUTDATED) {
                     imp.update_section.append(item.clone());
                 } else {
                     imp.status_section.append(item.clone());
@@ -798,8 +934,11 @@ impl PacViewWindow {
             let mut aur_names: HashSet<&str> = HashSet::with_capacity(n_lines);
             aur_names.extend(aur_file.lines());
 
-            // Get paru repo package map
-            let paru_map = ParuConf::local_pkg_map();
+            // Get AUR helper local repo package map
+            let helper_map = AurHelper::active().local_pkg_map();
+
+            // Get package repo map from previous load
+            let prev_repo_map = RepoMapFile::load();
 
             let syncdbs = alpm_handle.syncdbs();
             let localdb = alpm_handle.localdb();
@@ -807,20 +946,76 @@ impl PacViewWindow {
             // Load pacman local packages
             let local_data: Vec<PkgData> = localdb.pkgs().iter()
                 .map(|pkg| {
-                    let repository = if let Some(repo) = paru_map.get(pkg.name()) {
+                    let sync_pkg = syncdbs.pkg(pkg.name()).ok();
+
+                    let repository = if let Some(repo) = helper_map.get(pkg.name()) {
                         repo.as_str()
                     } else if aur_names.contains(pkg.name()) {
                         "aur"
                     } else {
-                        syncdbs.pkg(pkg.name()).ok()
+                        sync_pkg
                             .and_then(|sync_pkg| sync_pkg.db())
                             .map_or("local", alpm::Db::name)
                     };
 
-                    PkgData::from_alpm(pkg, true, repository)
+                    let mut data = PkgData::from_alpm(pkg, true, repository);
+
+                    let repo_sync_pkg = sync_pkg
+                        .filter(|sync_pkg| sync_pkg.db().is_some_and(|db| db.name() == repository));
+
+                    let prev_repo = prev_repo_map.get(pkg.name()).map(String::as_str);
+
+                    // Detect version skew against sync databases
+                    if let Some(sync_pkg) = repo_sync_pkg {
+                        if pkg.version().vercmp(sync_pkg.version()) == Ordering::Greater {
+                            data.flags |= PkgFlags::NEWER;
+                        }
+
+                        if prev_repo.is_some_and(|prev_repo| prev_repo != repository && prev_repo != "aur") {
+                            data.flags |= PkgFlags::MOVED;
+                        }
+                    } else if repository == "local" && pkg.validation().intersects(alpm::PackageValidation::SIGNATURE) {
+                        data.flags |= PkgFlags::DROPPED;
+                    }
+
+                    // Classify package origin
+                    data.origin = if repository == "aur" {
+                        PkgOrigin::Aur
+                    } else if repository != "local" {
+                        // Only compare packagers of identical versions (packager may change between releases)
+                        repo_sync_pkg
+                            .filter(|sync_pkg| pkg.version().vercmp(sync_pkg.version()) == Ordering::Equal)
+                            .and_then(|sync_pkg| sync_pkg.packager())
+                            .filter(|&packager| pkg.packager() != Some(packager))
+                            .map_or(PkgOrigin::Repository, |packager| PkgOrigin::Repackaged(packager.to_owned()))
+                    } else if prev_repo == Some("aur") && !aur_names.is_empty() {
+                        PkgOrigin::AurDeleted
+                    } else if let Some(prev_repo) = prev_repo.filter(|&prev_repo| prev_repo != "aur" && !syncdbs.iter().any(|db| db.name() == prev_repo)) {
+                        PkgOrigin::RemovedRepo(prev_repo.to_owned())
+                    } else if pkg.packager().is_none_or(|packager| packager == "Unknown Packager")
+                        || PkgbuildSource::clone_dir(repository, pkg.base().unwrap_or(pkg.name())).is_some() {
+                        PkgOrigin::LocalBuild
+                    } else {
+                        PkgOrigin::Unknown
+                    };
+
+                    data
                 })
                 .collect();
 
+            // Save package repo map for next load (keep repo from when package was first recorded)
+            RepoMapFile::save(
+                local_data.iter()
+                    .filter_map(|data| {
+                        prev_repo_map.get_key_value(&data.name)
+                            .map(|(name, repo)| (name.as_str(), repo.as_str()))
+                            .or_else(|| {
+                                (data.repository != "local")
+                                    .then_some((data.name.as_str(), data.repository.as_str()))
+                            })
+                    })
+            );
+
             sender.send_blocking((local_data, true))
                 .expect("Failed to send through channel");
 
@@ -882,14 +1077,16 @@ impl PacViewWindow {
 
                 match result {
                     Ok(()) => {
-                        // Get package updates
-                        window.get_package_updates().await;
+                        // Get package updates and AUR metadata
+                        join!(window.get_package_updates(), window.get_aur_metadata());
 
                         // Check AUR package names file age
                         let max_age = imp.prefs_dialog.borrow().aur_database_age() as u64;
 
-                        if aur_download && AurDBFile::out_of_date(max_age) {
-                            let _ = AurDBFile::download().await;
+                        if aur_download && AurDBFile::out_of_date(max_age) && !imp.aur_download_cancelled.get()
+                            && imp.aur_download_token.borrow().is_none()
+                        {
+                            let _ = window.download_aur_database().await;
                         }
                     },
                     Err(error) => {
@@ -908,6 +1105,44 @@ impl PacViewWindow {
         ));
     }
 
+    //---------------------------------------
+    // Download AUR database
+    //---------------------------------------
+    #[allow(clippy::future_not_send)]
+    async fn download_aur_database(&self) -> io::Result<()> {
+        let imp = self.imp();
+
+        let cache_dir = AurDBFile::cache_dir()
+            .ok_or_else(|| io::Error::other("Failed to retrieve AUR database path"))?;
+
+        // Create and store download cancel token
+        let cancel_token = CancellationToken::new();
+
+        imp.aur_download_token.replace(Some(cancel_token.clone()));
+
+        // Update package view progress bar
+        let (sender, receiver) = async_channel::unbounded::<(u64, Option<u64>)>();
+
+        let package_view = imp.package_view.get();
+
+        glib::spawn_future_local(async move {
+            while let Ok((received, total)) = receiver.recv().await {
+                package_view.set_download_progress(received, total);
+            }
+        });
+
+        let result = AurDBFile::download(&AurPage::url(), cache_dir, cancel_token, Some(sender)).await;
+
+        // Remove stored download cancel token
+        imp.aur_download_token.replace(None);
+
+        if result.is_ok() {
+            imp.aur_download_cancelled.set(false);
+        }
+
+        result
+    }
+
     //---------------------------------------
     // Cancel package updates
     //---------------------------------------
@@ -940,9 +1175,11 @@ impl PacViewWindow {
 
         let alpm_task = TokioUtils::run("/usr/bin/checkupdates", &[""], Some(alpm_token));
 
-        let (alpm_result, aur_result) = if let Ok(paru_path) = Paths::paru() {
+        let helper = AurHelper::active();
+
+        let (alpm_result, aur_result) = if let Some(helper_path) = helper.path() {
             // Check for AUR updates
-            let aur_task = TokioUtils::run(paru_path, &["-Qu", "--mode=ap"], Some(aur_token));
+            let aur_task = TokioUtils::run(helper_path, helper.update_args(), Some(aur_token));
 
             join!(alpm_task, aur_task)
         } else {
@@ -952,6 +1189,9 @@ impl PacViewWindow {
         // Remove stored update cancel token
         imp.update_cancel_token.replace(None);
 
+        // AUR helper returns exit code 1 if there are no updates
+        let aur_checked = helper.path().is_some() && aur_result.as_ref().is_ok_and(|(code, _)| code.is_some());
+
         // Get pacman update results
         match alpm_result {
             Ok((Some(0), stdout)) => {
@@ -992,12 +1232,12 @@ impl PacViewWindow {
             .collect();
 
         // Update status of packages with updates
-        if !update_map.is_empty() {
-            imp.package_view.show_updates(&update_map);
-        }
+        imp.package_view.show_updates(&update_map, aur_checked);
 
-        // Update info pane package if it has update
-        if imp.info_pane.pkg().is_some_and(|pkg| update_map.contains_key(&pkg.name().as_str())) {
+        // Update info pane package if it has update or AUR update check finished
+        if imp.info_pane.pkg().is_some_and(|pkg| {
+            update_map.contains_key(&pkg.name().as_str()) || (aur_checked && pkg.repository() == "aur")
+        }) {
             imp.info_pane.update_display();
         }
 
@@ -1012,6 +1252,31 @@ impl PacViewWindow {
         }
     }
 
+    //---------------------------------------
+    // Setup alpm: get AUR metadata
+    //---------------------------------------
+    #[allow(clippy::future_not_send)]
+    async fn get_aur_metadata(&self) {
+        let imp = self.imp();
+
+        // Merge AUR metadata into installed AUR packages
+        if imp.package_view.show_aur_metadata().await.is_err() {
+            return
+        }
+
+        // Update info pane package if it is an installed AUR package
+        if imp.info_pane.pkg().is_some_and(|pkg| pkg.is_installed() && pkg.repository() == "aur") {
+            imp.info_pane.update_display();
+        }
+
+        // If AUR status item is selected, refresh package status filter
+        let status_id = imp.package_view.status_id();
+
+        if status_id.intersects(PkgFlags::ORPHANED | PkgFlags::OUTDATED) {
+            imp.package_view.status_filter_changed(status_id);
+        }
+    }
+
     //---------------------------------------
     // Setup INotify
     //---------------------------------------